@@ -84,7 +84,7 @@ fn setup(
     let player_entity = commands
         .spawn((
             Player,
-            Inventory,
+            Inventory::default(),
             PlayerFactionInfo {
                 faction: Faction::EC,
                 rank: Rank::R7,
@@ -1,3 +1,4 @@
+use super::item_catalog::{ItemCatalogAsset, ItemCatalogLoader};
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 use bevy_kira_audio::AudioSource;
@@ -55,6 +56,12 @@ pub struct PlayerAssets {
     pub idle: Handle<AnimationClip>,
 }
 
+#[derive(Resource, AssetCollection)]
+pub struct ItemAssets {
+    #[asset(path = "items/catalog.ron")]
+    pub catalog: Handle<ItemCatalogAsset>,
+}
+
 #[derive(Resource, AssetCollection)]
 pub struct TextureAssets {
     #[asset(path = "starmap/nebula.png")]
@@ -65,15 +72,32 @@ pub struct AssetLoadingPlugin;
 
 impl Plugin for AssetLoadingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_loading_state(
-            bevy_asset_loader::prelude::LoadingState::new(GameState::Init)
-                .load_collection::<PlayerAssets>()
-                .load_collection::<MeshAssets>()
-                .load_collection::<TextureAssets>()
-                .load_collection::<AudioAssets>()
-                .load_collection::<FontAssets>()
-                .load_collection::<SceneAssets>()
-                .continue_to_state(GameState::Login),
-        );
+        app.init_asset::<ItemCatalogAsset>()
+            .init_asset_loader::<ItemCatalogLoader>()
+            .add_loading_state(
+                bevy_asset_loader::prelude::LoadingState::new(GameState::Init)
+                    .load_collection::<ItemAssets>()
+                    .load_collection::<PlayerAssets>()
+                    .load_collection::<MeshAssets>()
+                    .load_collection::<TextureAssets>()
+                    .load_collection::<AudioAssets>()
+                    .load_collection::<FontAssets>()
+                    .load_collection::<SceneAssets>()
+                    .continue_to_state(GameState::Login),
+            )
+            .add_systems(OnExit(GameState::Init), insert_item_catalog);
     }
 }
+
+fn insert_item_catalog(
+    item_assets: Res<ItemAssets>,
+    catalogs: Res<Assets<ItemCatalogAsset>>,
+    mut commands: Commands,
+) -> Result {
+    let catalog = catalogs
+        .get(&item_assets.catalog)
+        .ok_or("item catalog is not loaded")?;
+    info!("Loaded {} items into the catalog", catalog.items().len());
+    commands.insert_resource((**catalog).clone());
+    Ok(())
+}
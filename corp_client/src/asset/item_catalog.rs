@@ -0,0 +1,34 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use corp_shared::prelude::*;
+
+/// [`ItemCatalog`] as loaded from its RON file, copied into the resource once loading finished.
+#[derive(Asset, TypePath, Deref, Debug)]
+pub struct ItemCatalogAsset(ItemCatalog);
+
+#[derive(Default)]
+pub struct ItemCatalogLoader;
+
+impl AssetLoader for ItemCatalogLoader {
+    type Asset = ItemCatalogAsset;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let catalog = ItemCatalog::from_ron(std::str::from_utf8(&bytes)?)?;
+        Ok(ItemCatalogAsset(catalog))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
@@ -1,6 +1,7 @@
 mod asset_loading;
+mod item_catalog;
 mod path;
 
 pub mod prelude {
-    pub use super::{asset_loading::*, path::ASSET_PATH};
+    pub use super::{asset_loading::*, item_catalog::*, path::ASSET_PATH};
 }
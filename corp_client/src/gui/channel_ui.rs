@@ -0,0 +1,72 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use corp_shared::prelude::*;
+
+#[derive(Component)]
+struct ChannelBarComponent;
+
+#[derive(Component)]
+struct ChannelBarFillComponent;
+
+/// Progress bar of the action the local player is channeling.
+pub struct ChannelUiPlugin;
+
+impl Plugin for ChannelUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup)
+            .add_observer(on_channel_progress)
+            .add_observer(on_channel_ended);
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(240.0),
+                height: Val::Px(14.0),
+                left: Val::Percent(50.0),
+                bottom: Val::Percent(25.0),
+                margin: UiRect::left(Val::Px(-120.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BorderColor(Color::srgb(0.9, 0.9, 0.9)),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            Visibility::Hidden,
+            ChannelBarComponent,
+            StateScoped(GameState::Playing),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Percent(0.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.8, 0.4)),
+                ChannelBarFillComponent,
+            ));
+        });
+}
+
+fn on_channel_progress(
+    trigger: Trigger<ChannelProgress>,
+    q_bar: Single<&mut Visibility, With<ChannelBarComponent>>,
+    q_fill: Single<&mut Node, With<ChannelBarFillComponent>>,
+) {
+    *q_bar.into_inner() = Visibility::Visible;
+    q_fill.into_inner().width = Val::Percent(trigger.event().progress * 100.0);
+}
+
+fn on_channel_ended(
+    trigger: Trigger<ChannelEnded>,
+    q_bar: Single<&mut Visibility, With<ChannelBarComponent>>,
+) {
+    let event = trigger.event();
+    if let Some(reason) = event.cancelled {
+        info!("{:?} cancelled: {:?}", event.action, reason);
+    }
+    *q_bar.into_inner() = Visibility::Hidden;
+}
@@ -6,7 +6,7 @@ pub struct GuiPlugin;
 
 impl Plugin for GuiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((DebugGuiPlugin, CursorPlugin, LoginPlugin))
+        app.add_plugins((DebugGuiPlugin, CursorPlugin, ChannelUiPlugin, LoginPlugin))
             .add_systems(OnEnter(GameState::Init), loading_splash);
     }
 }
@@ -1,8 +1,9 @@
+mod channel_ui;
 mod cursor_ui;
 mod debug;
 mod gui;
 mod login;
 
 pub mod prelude {
-    pub use super::{cursor_ui::*, debug::*, gui::*, login::*};
+    pub use super::{channel_ui::*, cursor_ui::*, debug::*, gui::*, login::*};
 }
@@ -25,6 +25,9 @@ struct RequestDisconnect;
 struct ConnectClientTo(pub Colony);
 #[derive(Event, Clone, Eq, PartialEq)]
 struct ConnectionDisconnectedEvent;
+/// Ticket from the server for the next colony the client connects to.
+#[derive(Resource, Deref)]
+struct TransferTicket(String);
 
 impl Plugin for ClientNetPlugin {
     fn build(&self, app: &mut App) {
@@ -47,7 +50,8 @@ impl Plugin for ClientNetPlugin {
         .add_observer(on_connected)
         .add_observer(request_disconnect)
         .add_observer(disconnect_and_exit)
-        .add_observer(on_disconnected);
+        .add_observer(on_disconnected)
+        .add_observer(on_transfer_to_colony);
     }
 }
 
@@ -83,12 +87,15 @@ fn connect_client(
     mut commands: Commands,
     client_settings: Res<ClientSettings>,
     token: Option<Res<AuthToken>>,
+    ticket: Option<Res<TransferTicket>>,
 ) -> Result {
     let colony = trigger.event().0;
     let config = client_settings.client_config();
 
     let token = token.ok_or("Auth token not found")?;
-    let connect_options = client_settings.target(&colony, &token);
+    let connect_options =
+        client_settings.target(&colony, &token, ticket.as_deref().map(|t| t.as_str()));
+    commands.remove_resource::<TransferTicket>();
     info!("client_connect with options: {connect_options:?}");
     commands
         .spawn((
@@ -191,6 +198,19 @@ fn on_disconnected(
     Ok(())
 }
 
+fn on_transfer_to_colony(
+    trigger: Trigger<TransferToColony>,
+    mut commands: Commands,
+    client_e: Single<Entity, With<CorpClient>>,
+) {
+    let transfer = trigger.event();
+    info!("Server transfers the player to {}", transfer.colony);
+    commands.insert_resource(TransferTicket(transfer.ticket.clone()));
+    commands
+        .entity(*client_e)
+        .trigger(RequestConnect(transfer.colony));
+}
+
 mod code {
     pub const APP_EXIT: &str = "APP_EXIT";
     pub const REQUEST_DISCONNECT: &str = "REQUEST_DISCONNECT";
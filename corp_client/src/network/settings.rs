@@ -26,11 +26,19 @@ impl Default for ClientSettings {
 }
 
 impl ClientSettings {
-    pub fn target(&self, route: &Colony, token: &AuthToken) -> ConnectOptions {
-        ConnectOptions::builder(&self.host)
+    pub fn target(
+        &self,
+        route: &Colony,
+        token: &AuthToken,
+        ticket: Option<&str>,
+    ) -> ConnectOptions {
+        let builder = ConnectOptions::builder(&self.host)
             .add_header("x-route", route)
-            .add_header("x-token", token)
-            .build()
+            .add_header("x-token", token);
+        match ticket {
+            Some(ticket) => builder.add_header("x-ticket", ticket).build(),
+            None => builder.build(),
+        }
     }
     pub fn client_config(&self) -> wtransport::ClientConfig {
         let config = wtransport::ClientConfig::builder().with_bind_default();
@@ -16,8 +16,6 @@ fn on_spawn_backpack(
     mut commands: Commands,
 ) -> Result {
     let mut entity_commands = commands.get_entity(trigger.target())?;
-    entity_commands
-        .insert((SceneRoot(r_mesh_assets.low_poly_backpack.clone()),))
-        .observe(on_use_backpack_event);
+    entity_commands.insert((SceneRoot(r_mesh_assets.low_poly_backpack.clone()),));
     Ok(())
 }
@@ -6,7 +6,7 @@ use bevy_dolly::{
     prelude::{Arm, *},
 };
 use bevy_enhanced_input::prelude::*;
-use bevy_replicon::prelude::ClientTriggerExt;
+use bevy_replicon::prelude::{ClientTriggerExt, Replicated};
 use bevy_tnua::{builtins::TnuaBuiltinWalk, controller::TnuaController};
 use corp_shared::prelude::*;
 use std::{f32::consts::PI, hash::Hash};
@@ -69,6 +69,7 @@ impl Plugin for ControlPlugin {
             .add_observer(apply_orientation_mode)
             .add_observer(apply_use)
             .add_observer(apply_kill)
+            .add_observer(apply_shoot)
             .add_observer(apply_inventory)
             .add_observer(apply_exit)
             .add_observer(apply_window_cursor_visible)
@@ -114,7 +115,6 @@ fn can_move(mut query: Query<(&mut CharacterMovement, &Health), Changed<Health>>
 const HORIZONTAL_FOV: f32 = 2.0 * PI / 3.0; // 120 degrees in radians
 const NUM_RAYS: usize = 10; // Number of rays to evenly distribute within the FOV
 const RAY_SPACING: f32 = HORIZONTAL_FOV / (NUM_RAYS - 1) as f32; // Angle between each ray
-const LOOKUP_RANGE: f32 = 2.0; // Range of rays
 
 fn detect_usable_targets(
     player_entity: Single<Entity, With<LocalPlayer>>,
@@ -130,8 +130,7 @@ fn detect_usable_targets(
 
     let mut rays = Vec::new();
     // Cast FOV rays
-    let offset_height = Vec3::Y / 2.0; // Adjustable height of rays origin
-    let origin = t_player.translation - offset_height;
+    let origin = use_origin(t_player.translation);
     for i in 0..NUM_RAYS {
         // Calculate the horizontal angle for the current ray
         let ray_angle = (i as f32 * RAY_SPACING) - (HORIZONTAL_FOV / 2.0);
@@ -177,7 +176,7 @@ fn detect_usable_targets(
             ray.direction.into(),
             LOOKUP_RANGE,
             true,
-            &SpatialQueryFilter::from_mask([GameLayer::Structure, GameLayer::Sensor]),
+            &SpatialQueryFilter::from_mask(USABLE_LAYERS),
         ) {
             if let Ok(_) = q_use.get(ray_hit_data.entity) {
                 gizmos.ray(
@@ -389,12 +388,22 @@ fn apply_use(
     _trigger: Trigger<Started<UseAction>>,
     mut commands: Commands,
     r_use_entity: Res<HoverEntities>,
-    player_entity: Single<Entity, With<LocalPlayer>>,
     q_use: Query<&Use>,
+    q_scene_replica: Query<&SceneReplica>,
+    q_replicated: Query<(), With<Replicated>>,
 ) -> Result {
     for entity_target in r_use_entity.iter() {
-        if q_use.contains(entity_target.entity) {
-            commands.trigger_targets(UseCommand::new(*player_entity), entity_target.entity);
+        if !q_use.contains(entity_target.entity) {
+            continue;
+        }
+        // Scene structures are used through the entity the server replicates for them
+        let target = q_scene_replica
+            .get(entity_target.entity)
+            .map_or(entity_target.entity, |replica| replica.0);
+        if q_replicated.contains(target) {
+            let request = UseRequest { target };
+            commands.client_trigger(request);
+            commands.trigger(request);
         }
     }
     Ok(())
@@ -405,16 +414,29 @@ fn apply_kill(_trigger: Trigger<Started<KillAction>>, mut commands: Commands) ->
     Ok(())
 }
 
+/// Fires towards the cursor, the server resolves what gets hit.
+fn apply_shoot(
+    _trigger: Trigger<Started<ShootAction>>,
+    r_cursor_world: Res<CursorWorld>,
+    player_tr: Single<&Transform, With<LocalPlayer>>,
+    mut commands: Commands,
+) {
+    let direction = r_cursor_world.0 - player_tr.translation;
+    if aim_direction(direction).is_some() {
+        commands.client_trigger(FireRequest { direction });
+    }
+}
+
 fn apply_inventory(
     _trigger: Trigger<Started<InventoryAction>>,
     container_query: Query<(&Name, &Contains), With<LocalPlayer>>,
-    q_item_name: Query<&Name, With<Item>>,
+    q_item_name: Query<(&Name, &Quantity), With<Item>>,
 ) {
     for (container_name, contains) in &container_query {
         println!("{:?} contains:", container_name);
         for item_entity in contains.iter() {
-            if let Ok(name) = q_item_name.get(item_entity) {
-                println!("  - {}", name);
+            if let Ok((name, quantity)) = q_item_name.get(item_entity) {
+                println!("  - {} x{}", name, quantity.0);
             }
         }
     }
@@ -433,26 +455,18 @@ fn apply_window_cursor_visible(
     window.cursor_options.visible = !window.cursor_options.visible;
 }
 
-fn apply_starmap_iris(
-    _trigger: Trigger<Started<ColonyIrisAction>>,
-    mut commands: Commands,
-    client_e: Single<Entity, With<CorpClient>>,
-) {
+fn apply_starmap_iris(_trigger: Trigger<Started<ColonyIrisAction>>, mut commands: Commands) {
     info!("apply_starmap_iris");
-    commands
-        .entity(*client_e)
-        .trigger(RequestConnect(Colony::Iris));
+    commands.client_trigger(ColonyTravelClientCommand {
+        colony: Colony::Iris,
+    });
 }
 
-fn apply_starmap_liberte(
-    _trigger: Trigger<Started<ColonyLiberteAction>>,
-    mut commands: Commands,
-    client_e: Single<Entity, With<CorpClient>>,
-) {
+fn apply_starmap_liberte(_trigger: Trigger<Started<ColonyLiberteAction>>, mut commands: Commands) {
     info!("apply_starmap_liberte");
-    commands
-        .entity(*client_e)
-        .trigger(RequestConnect(Colony::Liberte));
+    commands.client_trigger(ColonyTravelClientCommand {
+        colony: Colony::Liberte,
+    });
 }
 
 fn apply_rotate_camera_clockwise(
@@ -12,16 +12,7 @@ impl Plugin for BarrierPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             FixedUpdate,
-            (change_barrier_field_visibility_and_collision,).run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(
-            FixedUpdate,
-            (
-                door_cooldown_system,
-                process_temporary_faction_ownership_timers_system,
-            )
-                .chain()
-                .run_if(in_state(GameState::Playing)),
+            change_barrier_field_visibility_and_collision.run_if(in_state(GameState::Playing)),
         )
         .add_observer(on_add_door);
     }
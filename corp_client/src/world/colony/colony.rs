@@ -9,9 +9,11 @@ impl Plugin for ColonyPlugin {
         app.add_plugins((
             VortexPlugin,
             BarrierPlugin,
+            ReplicaPlugin,
             StructurePlugin,
             AreaPlugin,
             ActionPlugin,
+            ItemCatalogPlugin,
             colony_loader_plugin,
         ));
     }
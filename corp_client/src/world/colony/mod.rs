@@ -1,9 +1,10 @@
 mod barrier;
 mod colony;
 mod colony_loader;
+mod replica;
 mod vortex;
 
 pub mod prelude {
-    pub use super::{barrier::*, colony::*, colony_loader::*, vortex::*};
+    pub use super::{barrier::*, colony::*, colony_loader::*, replica::*, vortex::*};
     pub use corp_shared::world::gameplay::area::*;
 }
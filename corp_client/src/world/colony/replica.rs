@@ -0,0 +1,76 @@
+use bevy::{ecs::component::Mutable, prelude::*};
+use corp_shared::prelude::*;
+
+/// Pairs the structures the server replicates with the ones the client loaded from the scene.
+pub struct ReplicaPlugin;
+
+impl Plugin for ReplicaPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                link_scene_replicas,
+                (
+                    sync_from_replica::<DoorState>,
+                    sync_from_replica::<EnergyNodeState>,
+                    sync_from_replica::<OwnershipRegistry>,
+                    sync_from_replica::<SecurityLevel>,
+                ),
+                process_temporary_faction_ownership_timers_system,
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_observer(unlink_scene_replica);
+    }
+}
+
+/// Replicated entity of a scene structure, requests to the server target it.
+#[derive(Component, Debug)]
+pub struct SceneReplica(pub Entity);
+
+/// Scene structure a replicated entity belongs to.
+#[derive(Component, Debug)]
+pub struct ReplicaOf(pub Entity);
+
+fn link_scene_replicas(
+    q_replica: Query<(Entity, &SceneNodeName), Without<ReplicaOf>>,
+    q_scene: Query<(Entity, &Name), (Without<SceneNodeName>, Without<SceneReplica>)>,
+    mut commands: Commands,
+) {
+    for (replica_e, scene_node_name) in &q_replica {
+        // The scene may still be loading, try again next tick
+        let Some((scene_e, _)) = q_scene
+            .iter()
+            .find(|(_, name)| name.as_str() == scene_node_name.0)
+        else {
+            continue;
+        };
+        commands.entity(scene_e).insert(SceneReplica(replica_e));
+        commands.entity(replica_e).insert(ReplicaOf(scene_e));
+    }
+}
+
+/// Structures are simulated on the server, the scene structures show the state of their replica.
+fn sync_from_replica<C: Component<Mutability = Mutable> + Clone>(
+    q_replica: Query<(&C, &ReplicaOf), Or<(Changed<C>, Added<ReplicaOf>)>>,
+    mut q_scene: Query<&mut C, Without<ReplicaOf>>,
+) {
+    for (component, replica_of) in &q_replica {
+        if let Ok(mut scene_component) = q_scene.get_mut(replica_of.0) {
+            *scene_component = component.clone();
+        }
+    }
+}
+
+fn unlink_scene_replica(
+    trigger: Trigger<OnRemove, ReplicaOf>,
+    q_replica_of: Query<&ReplicaOf>,
+    mut commands: Commands,
+) -> Result {
+    let replica_of = q_replica_of.get(trigger.target())?;
+    if let Ok(mut scene_entity) = commands.get_entity(replica_of.0) {
+        scene_entity.remove::<SceneReplica>();
+    }
+    Ok(())
+}
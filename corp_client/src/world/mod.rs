@@ -1,7 +1,7 @@
 mod animator;
 mod ccc;
-mod cloning;
 mod colony;
+mod movement;
 mod physics;
 mod player;
 mod shader;
@@ -10,7 +10,7 @@ mod world;
 
 pub mod prelude {
     pub use super::{
-        animator::*, ccc::*, cloning::*, colony::prelude::*, physics::*, player::*, shader::*,
+        animator::*, ccc::*, colony::prelude::*, movement::*, physics::*, player::*, shader::*,
         star_map::*, world::*,
     };
 }
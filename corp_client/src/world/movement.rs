@@ -0,0 +1,58 @@
+use crate::prelude::*;
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy_replicon::{
+    bytes::Bytes,
+    prelude::*,
+    shared::replication::{
+        deferred_entity::DeferredEntity,
+        replication_registry::{command_fns, ctx::WriteCtx, rule_fns::RuleFns},
+    },
+};
+use corp_shared::prelude::*;
+use std::time::Duration;
+
+const PLAYER_MOVE_SEND_SECS: f32 = 0.1;
+
+/// Reports the movement of the [`LocalPlayer`] to the server, which only limits how far it moves.
+pub struct PlayerMovementPlugin;
+
+impl Plugin for PlayerMovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_marker::<LocalPlayer>()
+            .set_marker_fns::<LocalPlayer, Transform>(
+                write_local_player_transform,
+                command_fns::default_remove::<Transform>,
+            )
+            .add_systems(
+                FixedUpdate,
+                send_local_player_transform
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(on_timer(Duration::from_secs_f32(PLAYER_MOVE_SEND_SECS))),
+            );
+    }
+}
+
+/// Local player movement is driven by the client, the server copy of its [`Transform`] is
+/// consumed without being applied.
+fn write_local_player_transform(
+    ctx: &mut WriteCtx,
+    rule_fns: &RuleFns<Transform>,
+    _entity: &mut DeferredEntity,
+    message: &mut Bytes,
+) -> Result<()> {
+    rule_fns.deserialize(ctx, message)?;
+    Ok(())
+}
+
+fn send_local_player_transform(
+    q_player_tr: Single<&Transform, (With<LocalPlayer>, Changed<Transform>)>,
+    mut sequence: Local<u32>,
+    mut commands: Commands,
+) {
+    *sequence += 1;
+    commands.client_trigger(PlayerMoveClientCommand {
+        sequence: *sequence,
+        translation: q_player_tr.translation,
+        rotation: q_player_tr.rotation,
+    });
+}
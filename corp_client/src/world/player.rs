@@ -4,7 +4,6 @@ use bevy::{prelude::*, scene::SceneInstanceReady};
 use bevy_tnua::prelude::TnuaController;
 use bevy_tnua_avian3d::TnuaAvian3dSensorShape;
 use corp_shared::prelude::*;
-use rand::seq::SliceRandom;
 
 /// Marks [`Player`] as locally controlled.
 #[derive(Component)]
@@ -14,14 +13,67 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(on_setup_local_player);
+        app.add_observer(on_setup_local_player)
+            .add_observer(on_rank_up)
+            .add_observer(on_hit_confirmed)
+            .add_observer(on_loot_contents)
+            .add_observer(on_loot_result)
+            .add_observer(on_inventory_rejected);
     }
 }
 
+fn on_rank_up(trigger: Trigger<RankUpEvent>) {
+    let event = trigger.event();
+    info!(
+        "Promoted to {} with {} experience",
+        event.rank, event.experience
+    );
+}
+
+fn on_hit_confirmed(
+    trigger: Trigger<HitConfirmed>,
+    q_local_player: Query<(), With<LocalPlayer>>,
+    q_name: Query<&CreatureName>,
+) {
+    let event = trigger.event();
+    let name = |entity| {
+        q_name
+            .get(entity)
+            .map_or_else(|_| entity.to_string(), ToString::to_string)
+    };
+    let blocked = if event.blocked { ", blocked" } else { "" };
+    if q_local_player.contains(event.shooter) {
+        info!("Hit {}{}", name(event.victim), blocked);
+    } else if q_local_player.contains(event.victim) {
+        info!("Hit by {}{}", name(event.shooter), blocked);
+    }
+}
+
+fn on_loot_contents(trigger: Trigger<LootContents>) {
+    let event = trigger.event();
+    info!("Backpack {} contains:", event.backpack);
+    for entry in &event.items {
+        match &entry.id {
+            Some(id) => info!("  - {} x{}", id.0, entry.quantity),
+            None => info!("  - {} x{}", entry.item, entry.quantity),
+        }
+    }
+}
+
+fn on_loot_result(trigger: Trigger<LootResult>) {
+    let event = trigger.event();
+    if let Some(error) = event.error {
+        warn!("Looting backpack {} failed: {:?}", event.backpack, error);
+    }
+}
+
+fn on_inventory_rejected(trigger: Trigger<InventoryRejected>) {
+    warn!("Inventory refused the item: {:?}", trigger.event().error);
+}
+
 fn on_setup_local_player(
     trigger: Trigger<SetupPlayerServerCommand>,
     r_player_assets: Res<PlayerAssets>,
-    mut q_vortex_node_pos: Query<&mut Transform, With<VortexNode>>,
     mut commands: Commands,
     q_local_player: Query<&LocalPlayer>,
 ) {
@@ -33,28 +85,15 @@ fn on_setup_local_player(
         error!("Tried to spawn a second local player");
     }
 
-    let rnd_node_position = q_vortex_node_pos
-        .iter_mut()
-        .map(|t| t.translation)
-        .collect::<Vec<Vec3>>()
-        .choose(&mut rand::thread_rng())
-        .map(|p| p.to_owned())
-        .unwrap_or_else(|| Vec3::new(1.0, 10.0, 1.0));
-
     commands
         .entity(player_e)
         .insert((
             Name::new("Player"),
             LocalPlayer,
-            Transform::from_translation(rnd_node_position + Vec3::Y),
+            Transform::from_translation(trigger.translation),
             Visibility::default(),
             MovementBundle::default(),
             MainCameraFollow,
-            Inventory,
-            PlayerFactionInfo {
-                faction: Faction::EC,
-                rank: Rank::R6,
-            },
             StateScoped(GameState::Playing),
             // Physics
             (
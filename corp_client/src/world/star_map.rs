@@ -6,15 +6,62 @@ pub struct StarMapPlugin;
 
 impl Plugin for StarMapPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::StarMap), setup_star_map);
+        app.add_systems(OnEnter(GameState::StarMap), setup_star_map)
+            .add_systems(
+                Update,
+                update_colony_control_text.run_if(in_state(GameState::StarMap)),
+            );
     }
 }
 
-fn setup_star_map(mut commands: Commands, texture_assets: Res<TextureAssets>) {
+#[derive(Component)]
+struct ColonyControlText;
+
+fn setup_star_map(
+    mut commands: Commands,
+    texture_assets: Res<TextureAssets>,
+    font_assets: Res<FontAssets>,
+) {
     commands.spawn((Camera2d, StateScoped(GameState::StarMap)));
     commands.spawn((
         Name::new("Star Map Background"),
         Sprite::from_image(texture_assets.nebula.clone()),
         StateScoped(GameState::StarMap),
     ));
+    commands.spawn((
+        Text::default(),
+        ColonyControlText,
+        TextFont::from_font(font_assets.default_font.clone()).with_font_size(20.0),
+        TextColor::WHITE,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        StateScoped(GameState::StarMap),
+    ));
+}
+
+fn update_colony_control_text(
+    q_controller: Query<&ColonyController>,
+    q_changed: Query<(), Changed<ColonyController>>,
+    q_added_text: Query<(), Added<ColonyControlText>>,
+    mut q_text: Query<&mut Text, With<ColonyControlText>>,
+) {
+    if q_changed.is_empty() && q_added_text.is_empty() {
+        return;
+    }
+    let mut controllers = q_controller.iter().collect::<Vec<_>>();
+    controllers.sort_by_key(|controller| controller.colony.to_string());
+    let lines = controllers
+        .iter()
+        .map(|controller| match controller.controller {
+            Some(faction) => format!("{}: {}", controller.colony, faction),
+            None => format!("{}: contested", controller.colony),
+        })
+        .collect::<Vec<_>>();
+    for mut text in &mut q_text {
+        text.0 = lines.join("\n");
+    }
 }
@@ -0,0 +1,13 @@
+-- Create characters table for player progress
+CREATE TABLE IF NOT EXISTS characters (
+    user_id INTEGER PRIMARY KEY,
+    hit_points REAL NOT NULL DEFAULT 100.0,
+    inventory TEXT NOT NULL DEFAULT '[]',
+    faction TEXT,
+    rank TEXT,
+    last_colony TEXT,
+    experience INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    last_login DATETIME,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+);
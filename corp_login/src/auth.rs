@@ -2,7 +2,7 @@ use anyhow::Result;
 use bcrypt::{hash, verify, DEFAULT_COST};
 use chrono::{Duration, Utc};
 use corp_types::prelude::*;
-use sqlx::SqlitePool;
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
 use tracing::{error, info};
 use uuid::Uuid;
 
@@ -90,27 +90,36 @@ pub async fn create_token_for_user(pool: &SqlitePool, user_id: i64) -> Result<To
     Ok(token)
 }
 
-pub async fn validate_token(pool: &SqlitePool, token_str: &str) -> Result<Option<(User, Token)>> {
-    // First clean up expired tokens
-    clean_expired_tokens(pool).await?;
-
-    // Get the token first
-    let token = sqlx::query_as::<_, Token>(
-        "SELECT id, user_id, token, created_at, expires_at FROM tokens WHERE token = ? AND expires_at > datetime('now')"
-    )
-    .bind(token_str)
-    .fetch_optional(pool)
-    .await?;
+/// Unexpired tokens joined with their users, filtered further by the caller.
+const ACTIVE_TOKENS_QUERY: &str = "SELECT users.id AS user_id, users.username, users.email, users.password, users.created_at AS user_created_at, users.updated_at, tokens.id AS token_id, tokens.token, tokens.created_at AS token_created_at, tokens.expires_at FROM tokens JOIN users ON users.id = tokens.user_id WHERE tokens.expires_at > datetime('now')";
+
+fn user_and_token(row: &SqliteRow) -> sqlx::Result<(User, Token)> {
+    let user = User {
+        id: row.try_get("user_id")?,
+        username: row.try_get("username")?,
+        email: row.try_get("email")?,
+        password: row.try_get("password")?,
+        created_at: row.try_get("user_created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    };
+    let token = Token {
+        id: row.try_get("token_id")?,
+        user_id: user.id,
+        token: row.try_get("token")?,
+        created_at: row.try_get("token_created_at")?,
+        expires_at: row.try_get("expires_at")?,
+    };
+    Ok((user, token))
+}
 
-    if let Some(token) = token {
-        // Get the user for this token
-        let user = sqlx::query_as::<_, User>(
-            "SELECT id, username, email, password, created_at, updated_at FROM users WHERE id = ?",
-        )
-        .bind(token.user_id)
-        .fetch_one(pool)
+pub async fn validate_token(pool: &SqlitePool, token_str: &str) -> Result<Option<(User, Token)>> {
+    let row = sqlx::query(&format!("{ACTIVE_TOKENS_QUERY} AND tokens.token = ?"))
+        .bind(token_str)
+        .fetch_optional(pool)
         .await?;
 
+    if let Some(row) = row {
+        let (user, token) = user_and_token(&row)?;
         info!("Token validated successfully for user: {}", user.username);
         Ok(Some((user, token)))
     } else {
@@ -119,6 +128,18 @@ pub async fn validate_token(pool: &SqlitePool, token_str: &str) -> Result<Option
     }
 }
 
+pub async fn get_active_tokens(pool: &SqlitePool) -> Result<Vec<(User, Token)>> {
+    let active_tokens = sqlx::query(ACTIVE_TOKENS_QUERY)
+        .fetch_all(pool)
+        .await?
+        .iter()
+        .map(user_and_token)
+        .collect::<sqlx::Result<Vec<_>>>()?;
+
+    info!("Loaded {} active tokens", active_tokens.len());
+    Ok(active_tokens)
+}
+
 pub async fn invalidate_token(pool: &SqlitePool, token_str: &str) -> Result<bool> {
     let result = sqlx::query("DELETE FROM tokens WHERE token = ?")
         .bind(token_str)
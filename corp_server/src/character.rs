@@ -0,0 +1,145 @@
+use crate::{handoff::PlayerSnapshot, table::CharacterRow};
+use corp_shared::prelude::Colony;
+use kameo::{
+    actor::{ActorRef, WeakActorRef},
+    error::Infallible,
+    prelude::{ActorStopReason, Context, Message},
+    Actor,
+};
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+/// Character of a user as it was last saved.
+#[derive(Debug, Clone)]
+pub struct StoredCharacter {
+    pub snapshot: PlayerSnapshot,
+    /// Colony the player was last saved in, logging in on the star map returns it there.
+    pub last_colony: Option<Colony>,
+}
+
+#[derive(Debug)]
+pub struct LoadCharacter(pub i64);
+
+#[derive(Debug)]
+pub struct SaveCharacter {
+    pub user_id: i64,
+    pub colony: Colony,
+    pub snapshot: PlayerSnapshot,
+}
+
+/// Loads and saves characters in the `characters` table of the login database.
+#[derive(Debug)]
+pub struct Characters {
+    pool: SqlitePool,
+}
+
+impl Characters {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn load(&self, user_id: i64) -> anyhow::Result<Option<StoredCharacter>> {
+        let row = sqlx::query_as::<_, CharacterRow>(
+            "SELECT user_id, hit_points, inventory, faction, rank, last_colony, experience, created_at, last_login FROM characters WHERE user_id = ?"
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        sqlx::query("UPDATE characters SET last_login = datetime('now') WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(Some(StoredCharacter {
+            snapshot: row.snapshot()?,
+            last_colony: row.last_colony(),
+        }))
+    }
+
+    async fn save(&self, msg: &SaveCharacter) -> anyhow::Result<()> {
+        let inventory = CharacterRow::inventory(&msg.snapshot.items)?;
+        let faction_info = msg.snapshot.faction_info.as_ref();
+        sqlx::query(
+            r#"
+            INSERT INTO characters (user_id, hit_points, inventory, faction, rank, experience, last_colony, last_login)
+            VALUES (?, ?, ?, ?, ?, ?, ?, datetime('now'))
+            ON CONFLICT (user_id) DO UPDATE SET
+                hit_points = excluded.hit_points,
+                inventory = excluded.inventory,
+                faction = excluded.faction,
+                rank = excluded.rank,
+                experience = excluded.experience,
+                last_colony = excluded.last_colony
+            "#,
+        )
+        .bind(msg.user_id)
+        .bind(f64::from(msg.snapshot.hit_points))
+        .bind(inventory)
+        .bind(faction_info.map(|info| info.faction.to_string()))
+        .bind(faction_info.map(|info| info.rank.to_string()))
+        .bind(i64::try_from(msg.snapshot.experience).unwrap_or(i64::MAX))
+        .bind(msg.colony.to_string())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl Actor for Characters {
+    type Args = Self;
+    type Error = Infallible;
+
+    async fn on_start(args: Self::Args, _actor_ref: ActorRef<Self>) -> Result<Self, Self::Error> {
+        info!("Characters started");
+        Ok(args)
+    }
+
+    async fn on_stop(
+        &mut self,
+        _actor_ref: WeakActorRef<Self>,
+        reason: ActorStopReason,
+    ) -> Result<(), Self::Error> {
+        info!("Characters stopping: {:?}", reason);
+        Ok(())
+    }
+}
+
+impl Message<LoadCharacter> for Characters {
+    type Reply = Option<StoredCharacter>;
+
+    fn handle(
+        &mut self,
+        msg: LoadCharacter,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            match self.load(msg.0).await {
+                Ok(character) => character,
+                Err(e) => {
+                    error!("Failed to load character of user {}: {:?}", msg.0, e);
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl Message<SaveCharacter> for Characters {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: SaveCharacter,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            if let Err(e) = self.save(&msg).await {
+                error!("Failed to save character of user {}: {:?}", msg.user_id, e);
+            }
+        }
+    }
+}
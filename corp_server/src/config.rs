@@ -1,16 +1,29 @@
-use crate::server::Tokens;
+use crate::{
+    metrics::MetricsRegistry,
+    server::{Characters, ControlBoard, Handoff, SessionRegistry, Tokens, TransferTickets},
+};
 use aeronet_webtransport::wtransport::Identity;
 use bevy::prelude::Resource;
-use corp_shared::prelude::Colony;
+use corp_shared::prelude::{Colony, ItemCatalog};
 use kameo::actor::ActorRef;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::PathBuf};
 
 #[derive(Resource, Debug)]
 pub struct GameServerConfig {
     pub colony: Colony,
     pub server_addr: SocketAddr,
     pub identity: Identity,
+    /// Directory the colony scenes are read from.
+    pub assets_dir: PathBuf,
+    /// Items of the game, loaded from the assets directory once at startup.
+    pub item_catalog: ItemCatalog,
     pub tokens_ref: ActorRef<Tokens>,
+    pub metrics_ref: ActorRef<MetricsRegistry>,
+    pub sessions_ref: ActorRef<SessionRegistry>,
+    pub handoff_ref: ActorRef<Handoff>,
+    pub characters_ref: ActorRef<Characters>,
+    pub tickets_ref: ActorRef<TransferTickets>,
+    pub control_ref: ActorRef<ControlBoard>,
 }
 
 impl Clone for GameServerConfig {
@@ -19,7 +32,15 @@ impl Clone for GameServerConfig {
             colony: self.colony.clone(),
             server_addr: self.server_addr.clone(),
             identity: self.identity.clone_identity(),
+            assets_dir: self.assets_dir.clone(),
+            item_catalog: self.item_catalog.clone(),
             tokens_ref: self.tokens_ref.clone(),
+            metrics_ref: self.metrics_ref.clone(),
+            sessions_ref: self.sessions_ref.clone(),
+            handoff_ref: self.handoff_ref.clone(),
+            characters_ref: self.characters_ref.clone(),
+            tickets_ref: self.tickets_ref.clone(),
+            control_ref: self.control_ref.clone(),
         }
     }
 }
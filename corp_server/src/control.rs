@@ -0,0 +1,78 @@
+use corp_shared::prelude::{Colony, Faction};
+use kameo::{
+    actor::{ActorRef, WeakActorRef},
+    error::Infallible,
+    prelude::{ActorStopReason, Context, Message},
+    Actor,
+};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Colony servers report the faction that controls them.
+#[derive(Debug)]
+pub struct ReportColonyControl {
+    pub colony: Colony,
+    pub controller: Option<Faction>,
+}
+
+/// Replies with the controller of every colony that reported one.
+#[derive(Debug)]
+pub struct ListColonyControl;
+
+/// Which faction controls each colony, shown on the star map.
+#[derive(Default, Debug)]
+pub struct ControlBoard {
+    controllers: HashMap<Colony, Option<Faction>>,
+}
+
+impl ControlBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Actor for ControlBoard {
+    type Args = Self;
+    type Error = Infallible;
+
+    async fn on_start(args: Self::Args, _actor_ref: ActorRef<Self>) -> Result<Self, Self::Error> {
+        info!("ControlBoard started");
+        Ok(args)
+    }
+
+    async fn on_stop(
+        &mut self,
+        _actor_ref: WeakActorRef<Self>,
+        reason: ActorStopReason,
+    ) -> Result<(), Self::Error> {
+        info!("ControlBoard stopping: {:?}", reason);
+        Ok(())
+    }
+}
+
+impl Message<ReportColonyControl> for ControlBoard {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: ReportColonyControl,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            info!("{} controlled by {:?}", msg.colony, msg.controller);
+            self.controllers.insert(msg.colony, msg.controller);
+        }
+    }
+}
+
+impl Message<ListColonyControl> for ControlBoard {
+    type Reply = HashMap<Colony, Option<Faction>>;
+
+    fn handle(
+        &mut self,
+        _msg: ListColonyControl,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.controllers.clone() }
+    }
+}
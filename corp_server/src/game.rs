@@ -6,7 +6,10 @@ use bevy::{
     MinimalPlugins,
 };
 use bevy_rand::{plugin::EntropyPlugin, prelude::WyRand};
-use corp_shared::{network::TICK_RATE, prelude::Colony};
+use corp_shared::{
+    network::TICK_RATE,
+    prelude::{ChannelPlugin, Colony, RollPlugin},
+};
 use kameo::{
     actor::{ActorRef, WeakActorRef},
     error::{Infallible, PanicError},
@@ -91,12 +94,24 @@ fn create_colony_game_server(game_server_config: GameServerConfig) {
             MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(wait_duration)),
             StatesPlugin,
             ServerNetPlugin,
-            LootPlugin,
-            HealthRemotePlugin,
+            TelemetryPlugin,
+            TransferPlugin,
+            (
+                ColonyScenePlugin,
+                UsablePlugin,
+                VortexPlugin,
+                SpawnPlugin,
+                ChannelPlugin,
+                ChannelRemotePlugin,
+                DoorPlugin,
+                TerritoryPlugin,
+            ),
+            (InventoryRemotePlugin, LootPlugin),
+            (HealthRemotePlugin, CombatPlugin),
             DeathPlugin,
             CloningRemotePlugin,
-            PlayersPlugin,
-            EntropyPlugin::<WyRand>::default(),
+            (PlayersPlugin, PlayerMovementPlugin, ProgressionRemotePlugin),
+            (EntropyPlugin::<WyRand>::default(), RollPlugin),
         ))
         .run();
 }
@@ -110,6 +125,9 @@ fn create_star_map_game_server(game_server_config: GameServerConfig) {
             MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(wait_duration)),
             StatesPlugin,
             ServerNetPlugin,
+            TelemetryPlugin,
+            TransferPlugin,
+            StarMapControlPlugin,
             EntropyPlugin::<WyRand>::default(),
         ))
         .run();
@@ -0,0 +1,244 @@
+use bevy::prelude::Entity;
+use corp_shared::prelude::{Colony, ItemId, PlayerFactionInfo};
+use kameo::{
+    actor::{ActorRef, WeakActorRef},
+    error::Infallible,
+    prelude::{ActorStopReason, Context, Message},
+    Actor, Reply,
+};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// State of a player that is carried over from one colony to the next.
+#[derive(Debug, Clone)]
+pub struct PlayerSnapshot {
+    pub hit_points: f32,
+    /// Carried stacks with their quantities.
+    pub items: Vec<(ItemId, u32)>,
+    pub faction_info: Option<PlayerFactionInfo>,
+    pub experience: u64,
+}
+
+/// Who holds the state of a player right now.
+#[derive(Debug)]
+enum Custody {
+    /// The player is in the world of a colony, only that session may deposit its state.
+    InColony { colony: Colony, client: Entity },
+    /// The player left a colony and nobody has claimed its state yet.
+    InTransit(PlayerSnapshot),
+}
+
+/// Written by a departing colony when the session of the player ends.
+#[derive(Debug)]
+pub struct DepositPlayer {
+    pub user_id: i64,
+    pub colony: Colony,
+    pub client: Entity,
+    /// `None` releases the player without state, e.g. when it left before it was spawned.
+    pub snapshot: Option<PlayerSnapshot>,
+}
+
+/// Asked by an arriving colony before it spawns the player.
+#[derive(Debug, Clone, Copy)]
+pub struct ClaimPlayer {
+    pub user_id: i64,
+    pub colony: Colony,
+    pub client: Entity,
+    /// Takes custody even if another session hasn't deposited yet, its state is lost.
+    pub force: bool,
+}
+
+#[derive(Reply, Debug)]
+pub enum ClaimResult {
+    /// Custody moved to the claiming session, with the state of the previous colony if any.
+    Claimed(Option<PlayerSnapshot>),
+    /// Another session still holds the player, ask again after it has deposited.
+    Pending,
+    /// The claiming session already holds the player, its state lives in the colony.
+    Held,
+}
+
+/// Moves player state between colony servers.
+///
+/// Custody of a player belongs to exactly one session at a time, deposits from sessions that
+/// lost custody are dropped, so a late disconnect can't overwrite state that was already claimed.
+#[derive(Default, Debug)]
+pub struct Handoff {
+    players: HashMap<i64, Custody>,
+}
+
+impl Handoff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn deposit(&mut self, msg: DepositPlayer) {
+        match self.players.get(&msg.user_id) {
+            Some(Custody::InColony { colony, client })
+                if *colony == msg.colony && *client == msg.client =>
+            {
+                info!("Player {} left {}", msg.user_id, msg.colony);
+                match msg.snapshot {
+                    Some(snapshot) => {
+                        self.players
+                            .insert(msg.user_id, Custody::InTransit(snapshot));
+                    }
+                    None => {
+                        self.players.remove(&msg.user_id);
+                    }
+                }
+            }
+            _ => {
+                warn!(
+                    "Dropping stale state of player {} from {}",
+                    msg.user_id, msg.colony
+                );
+            }
+        }
+    }
+
+    fn claim(&mut self, msg: ClaimPlayer) -> ClaimResult {
+        let snapshot = match self.players.remove(&msg.user_id) {
+            None => None,
+            Some(Custody::InTransit(snapshot)) => Some(snapshot),
+            Some(custody @ Custody::InColony { colony, client })
+                if colony == msg.colony && client == msg.client =>
+            {
+                self.players.insert(msg.user_id, custody);
+                return ClaimResult::Held;
+            }
+            Some(custody @ Custody::InColony { .. }) if !msg.force => {
+                self.players.insert(msg.user_id, custody);
+                return ClaimResult::Pending;
+            }
+            Some(Custody::InColony { colony, .. }) => {
+                warn!(
+                    "Player {} was never released by {}, claiming without state",
+                    msg.user_id, colony
+                );
+                None
+            }
+        };
+        info!("Player {} arrived in {}", msg.user_id, msg.colony);
+        self.players.insert(
+            msg.user_id,
+            Custody::InColony {
+                colony: msg.colony,
+                client: msg.client,
+            },
+        );
+        ClaimResult::Claimed(snapshot)
+    }
+}
+
+impl Actor for Handoff {
+    type Args = Self;
+    type Error = Infallible;
+
+    async fn on_start(args: Self::Args, _actor_ref: ActorRef<Self>) -> Result<Self, Self::Error> {
+        info!("Handoff started");
+        Ok(args)
+    }
+
+    async fn on_stop(
+        &mut self,
+        _actor_ref: WeakActorRef<Self>,
+        reason: ActorStopReason,
+    ) -> Result<(), Self::Error> {
+        info!("Handoff stopping: {:?}", reason);
+        Ok(())
+    }
+}
+
+impl Message<DepositPlayer> for Handoff {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: DepositPlayer,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.deposit(msg) }
+    }
+}
+
+impl Message<ClaimPlayer> for Handoff {
+    type Reply = ClaimResult;
+
+    fn handle(
+        &mut self,
+        msg: ClaimPlayer,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.claim(msg) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_claim_of_session_is_held() {
+        // given
+        let mut handoff = Handoff::new();
+        let claim = ClaimPlayer {
+            user_id: 1,
+            colony: Colony::Liberte,
+            client: Entity::from_raw(1),
+            force: false,
+        };
+        handoff.claim(claim);
+
+        // when
+        let result = handoff.claim(claim);
+
+        // then
+        assert!(matches!(result, ClaimResult::Held));
+        handoff.deposit(DepositPlayer {
+            user_id: 1,
+            colony: Colony::Liberte,
+            client: Entity::from_raw(1),
+            snapshot: Some(snapshot()),
+        });
+        assert!(matches!(
+            handoff.claim(ClaimPlayer {
+                client: Entity::from_raw(2),
+                ..claim
+            }),
+            ClaimResult::Claimed(Some(_))
+        ));
+    }
+
+    #[test]
+    fn claim_of_other_session_waits_for_deposit() {
+        // given
+        let mut handoff = Handoff::new();
+        let claim = ClaimPlayer {
+            user_id: 1,
+            colony: Colony::Liberte,
+            client: Entity::from_raw(1),
+            force: false,
+        };
+        handoff.claim(claim);
+
+        // when
+        let result = handoff.claim(ClaimPlayer {
+            colony: Colony::Iris,
+            client: Entity::from_raw(2),
+            ..claim
+        });
+
+        // then
+        assert!(matches!(result, ClaimResult::Pending));
+    }
+
+    fn snapshot() -> PlayerSnapshot {
+        PlayerSnapshot {
+            hit_points: 100.0,
+            items: Vec::new(),
+            faction_info: None,
+            experience: 0,
+        }
+    }
+}
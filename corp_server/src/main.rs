@@ -1,22 +1,34 @@
-use crate::{login::LoginActor, proxy::ProxyActor, server::*};
+use crate::{login::LoginActor, metrics::MetricsRegistry, proxy::ProxyActor, server::*};
 use aeronet_webtransport::wtransport::Identity;
+use anyhow::Context;
 use bevy::ecs::error::{warn, GLOBAL_ERROR_HANDLER};
 use corp_shared::prelude::*;
 use corp_types::prelude::*;
 use game::GameServerActor;
 use kameo::Actor;
 use kameo_actors::pubsub::{PubSub, Subscribe};
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
 use tracing::info;
 
+mod character;
 mod config;
+mod control;
 mod game;
+mod handoff;
 pub mod login;
+mod metrics;
 mod proxy;
 pub mod server;
+mod session;
 mod table;
+mod ticket;
 mod token;
 
+const ASSETS_DIR_VAR: &str = "CORP_ASSETS_DIR";
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     init_logging()?;
@@ -24,6 +36,19 @@ async fn main() -> Result<(), anyhow::Error> {
         .set(warn)
         .expect("The error handler can only be set once, globally.");
     let identity = Identity::load_pemfiles("./certs/server.pem", "./certs/server.key").await?;
+    let assets_dir = assets_dir();
+    info!("Reading assets from {}", assets_dir.display());
+    let item_catalog_path = assets_dir.join(ITEM_CATALOG_PATH);
+    let item_catalog = ItemCatalog::load(&item_catalog_path).with_context(|| {
+        format!(
+            "Failed to load item catalog from {}",
+            item_catalog_path.display()
+        )
+    })?;
+    info!(
+        "Loaded {} items into the catalog",
+        item_catalog.items().len()
+    );
 
     let auth_pub_sub_ref = PubSub::spawn(PubSub::<AuthenticationEvent>::new());
     auth_pub_sub_ref.register("auth_pub_sub")?;
@@ -33,34 +58,89 @@ async fn main() -> Result<(), anyhow::Error> {
     let login_ref = LoginActor::spawn(LoginActor::new(auth_pub_sub_ref.clone()));
     login_ref.register("login")?;
 
-    let tokens_ref = Tokens::spawn(Tokens::new());
+    let sessions_ref = SessionRegistry::spawn(SessionRegistry::new());
+    sessions_ref.register("sessions")?;
+
+    let login_pool = corp_login::database::setup_database().await?;
+    let characters_ref = Characters::spawn(Characters::new(login_pool.clone()));
+    characters_ref.register("characters")?;
+
+    let tokens_ref = Tokens::spawn(Tokens::new(
+        login_pool,
+        auth_pub_sub_ref.clone(),
+        sessions_ref.clone(),
+    ));
     tokens_ref.register("tokens")?;
     auth_pub_sub_ref.ask(Subscribe(tokens_ref.clone())).await?;
 
+    let tickets_ref = TransferTickets::spawn(TransferTickets::new());
+    tickets_ref.register("tickets")?;
+
+    let handoff_ref = Handoff::spawn(Handoff::new());
+    handoff_ref.register("handoff")?;
+
+    let metrics_ref = MetricsRegistry::spawn(MetricsRegistry::new());
+    metrics_ref.register("metrics")?;
+
+    let control_ref = ControlBoard::spawn(ControlBoard::new());
+    control_ref.register("control")?;
+
     let game_server_configs = vec![
         GameServerConfig {
             colony: Colony::Iris,
             server_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 25565),
             identity: identity.clone_identity(),
+            assets_dir: assets_dir.clone(),
+            item_catalog: item_catalog.clone(),
             tokens_ref: tokens_ref.clone(),
+            metrics_ref: metrics_ref.clone(),
+            sessions_ref: sessions_ref.clone(),
+            handoff_ref: handoff_ref.clone(),
+            characters_ref: characters_ref.clone(),
+            tickets_ref: tickets_ref.clone(),
+            control_ref: control_ref.clone(),
         },
         GameServerConfig {
             colony: Colony::Cloning,
             server_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 25566),
             identity: identity.clone_identity(),
+            assets_dir: assets_dir.clone(),
+            item_catalog: item_catalog.clone(),
             tokens_ref: tokens_ref.clone(),
+            metrics_ref: metrics_ref.clone(),
+            sessions_ref: sessions_ref.clone(),
+            handoff_ref: handoff_ref.clone(),
+            characters_ref: characters_ref.clone(),
+            tickets_ref: tickets_ref.clone(),
+            control_ref: control_ref.clone(),
         },
         GameServerConfig {
             colony: Colony::StarMap,
             server_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 25567),
             identity: identity.clone_identity(),
+            assets_dir: assets_dir.clone(),
+            item_catalog: item_catalog.clone(),
             tokens_ref: tokens_ref.clone(),
+            metrics_ref: metrics_ref.clone(),
+            sessions_ref: sessions_ref.clone(),
+            handoff_ref: handoff_ref.clone(),
+            characters_ref: characters_ref.clone(),
+            tickets_ref: tickets_ref.clone(),
+            control_ref: control_ref.clone(),
         },
         GameServerConfig {
             colony: Colony::Liberte,
             server_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 25568),
             identity: identity.clone_identity(),
+            assets_dir: assets_dir.clone(),
+            item_catalog: item_catalog.clone(),
             tokens_ref: tokens_ref.clone(),
+            metrics_ref: metrics_ref.clone(),
+            sessions_ref: sessions_ref.clone(),
+            handoff_ref: handoff_ref.clone(),
+            characters_ref: characters_ref.clone(),
+            tickets_ref: tickets_ref.clone(),
+            control_ref: control_ref.clone(),
         },
     ];
 
@@ -75,3 +155,11 @@ async fn main() -> Result<(), anyhow::Error> {
     info!("Server shutdown complete.");
     Ok(())
 }
+
+/// Assets shared with the client, `CORP_ASSETS_DIR` overrides the client assets of this checkout.
+fn assets_dir() -> PathBuf {
+    std::env::var_os(ASSETS_DIR_VAR).map_or_else(
+        || PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../corp_client/assets"),
+        PathBuf::from,
+    )
+}
@@ -0,0 +1,239 @@
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use corp_shared::prelude::Colony;
+use kameo::{
+    actor::{ActorRef, WeakActorRef},
+    error::Infallible,
+    prelude::{ActorStopReason, Context, Message},
+    Actor,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+use tracing::{error, info};
+
+const METRICS_ADDR: &str = "127.0.0.1:25551";
+
+/// Snapshot of the numbers a colony server reports about itself.
+#[derive(Debug, Clone, Default)]
+pub struct ColonyMetrics {
+    pub colony: Colony,
+    pub tick_budget_secs: f64,
+    /// Tick duration in seconds keyed by quantile, e.g. `0.99`.
+    pub tick_duration_secs: Vec<(f64, f64)>,
+    pub entities: BTreeMap<&'static str, usize>,
+    pub connected_sessions: usize,
+    /// Bytes sent per second keyed by the user id of the client. Only connected clients are
+    /// reported, so the series of a client ends with its session.
+    pub replication_bytes_per_sec: BTreeMap<i64, f64>,
+    /// Bytes sent per second to all sessions together, including those not admitted yet.
+    pub replication_bytes_per_sec_total: f64,
+    /// Triggers received since start keyed by trigger type.
+    pub client_triggers_total: BTreeMap<&'static str, u64>,
+}
+
+#[derive(Debug)]
+pub struct ReportColonyMetrics(pub ColonyMetrics);
+
+#[derive(Debug)]
+pub struct RenderMetrics;
+
+/// Shared registry of colony metrics, served as Prometheus text on [`METRICS_ADDR`].
+#[derive(Default, Debug)]
+pub struct MetricsRegistry {
+    colonies: HashMap<Colony, ColonyMetrics>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn render(&self) -> String {
+        let mut colonies = self.colonies.values().collect::<Vec<_>>();
+        colonies.sort_by_key(|metrics| metrics.colony.to_string());
+
+        let mut out = String::new();
+        write_header(
+            &mut out,
+            "corp_tick_budget_seconds",
+            "gauge",
+            "Time budget of a single colony tick.",
+        );
+        for metrics in &colonies {
+            let _ = writeln!(
+                out,
+                "corp_tick_budget_seconds{{colony=\"{}\"}} {}",
+                metrics.colony, metrics.tick_budget_secs
+            );
+        }
+
+        write_header(
+            &mut out,
+            "corp_tick_duration_seconds",
+            "summary",
+            "Duration of colony ticks.",
+        );
+        for metrics in &colonies {
+            for (quantile, secs) in &metrics.tick_duration_secs {
+                let _ = writeln!(
+                    out,
+                    "corp_tick_duration_seconds{{colony=\"{}\",quantile=\"{}\"}} {}",
+                    metrics.colony, quantile, secs
+                );
+            }
+        }
+
+        write_header(
+            &mut out,
+            "corp_entities",
+            "gauge",
+            "Number of entities by archetype.",
+        );
+        for metrics in &colonies {
+            for (archetype, count) in &metrics.entities {
+                let _ = writeln!(
+                    out,
+                    "corp_entities{{colony=\"{}\",archetype=\"{}\"}} {}",
+                    metrics.colony, archetype, count
+                );
+            }
+        }
+
+        write_header(
+            &mut out,
+            "corp_connected_sessions",
+            "gauge",
+            "Number of connected sessions.",
+        );
+        for metrics in &colonies {
+            let _ = writeln!(
+                out,
+                "corp_connected_sessions{{colony=\"{}\"}} {}",
+                metrics.colony, metrics.connected_sessions
+            );
+        }
+
+        write_header(
+            &mut out,
+            "corp_replication_bytes_per_second",
+            "gauge",
+            "Bytes sent to a client per second.",
+        );
+        for metrics in &colonies {
+            for (user_id, bytes) in &metrics.replication_bytes_per_sec {
+                let _ = writeln!(
+                    out,
+                    "corp_replication_bytes_per_second{{colony=\"{}\",user_id=\"{}\"}} {}",
+                    metrics.colony, user_id, bytes
+                );
+            }
+        }
+
+        write_header(
+            &mut out,
+            "corp_replication_bytes_per_second_total",
+            "gauge",
+            "Bytes sent to all clients of a colony per second.",
+        );
+        for metrics in &colonies {
+            let _ = writeln!(
+                out,
+                "corp_replication_bytes_per_second_total{{colony=\"{}\"}} {}",
+                metrics.colony, metrics.replication_bytes_per_sec_total
+            );
+        }
+
+        write_header(
+            &mut out,
+            "corp_client_triggers_total",
+            "counter",
+            "Triggers received from clients by type.",
+        );
+        for metrics in &colonies {
+            for (trigger, count) in &metrics.client_triggers_total {
+                let _ = writeln!(
+                    out,
+                    "corp_client_triggers_total{{colony=\"{}\",trigger=\"{}\"}} {}",
+                    metrics.colony, trigger, count
+                );
+            }
+        }
+
+        out
+    }
+}
+
+fn write_header(out: &mut String, name: &str, metric_type: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {metric_type}");
+}
+
+impl Actor for MetricsRegistry {
+    type Args = Self;
+    type Error = Infallible;
+
+    async fn on_start(args: Self::Args, actor_ref: ActorRef<Self>) -> Result<Self, Self::Error> {
+        info!("MetricsRegistry started");
+        tokio::spawn(async move {
+            if let Err(e) = serve_metrics(actor_ref).await {
+                error!("Metrics endpoint stopped: {:?}", e);
+            }
+        });
+        Ok(args)
+    }
+
+    async fn on_stop(
+        &mut self,
+        _actor_ref: WeakActorRef<Self>,
+        reason: ActorStopReason,
+    ) -> Result<(), Self::Error> {
+        info!("MetricsRegistry stopping: {:?}", reason);
+        Ok(())
+    }
+}
+
+impl Message<ReportColonyMetrics> for MetricsRegistry {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: ReportColonyMetrics,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            self.colonies.insert(msg.0.colony, msg.0);
+        }
+    }
+}
+
+impl Message<RenderMetrics> for MetricsRegistry {
+    type Reply = String;
+
+    fn handle(
+        &mut self,
+        _msg: RenderMetrics,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.render() }
+    }
+}
+
+async fn serve_metrics(metrics_ref: ActorRef<MetricsRegistry>) -> anyhow::Result<()> {
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(metrics_ref);
+    let listener = tokio::net::TcpListener::bind(METRICS_ADDR).await?;
+    info!("Metrics available on http://{METRICS_ADDR}/metrics");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn metrics_handler(
+    State(metrics_ref): State<ActorRef<MetricsRegistry>>,
+) -> Result<String, StatusCode> {
+    metrics_ref.ask(RenderMetrics).await.map_err(|e| {
+        error!("Failed to render metrics: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
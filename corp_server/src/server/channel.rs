@@ -0,0 +1,76 @@
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+use std::time::Duration;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Keeps players informed about the actions they are channeling.
+pub struct ChannelRemotePlugin;
+
+impl Plugin for ChannelRemotePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            send_channel_progress
+                .after(channel_system)
+                .run_if(on_timer(PROGRESS_INTERVAL)),
+        )
+        .add_observer(on_channel_completed)
+        .add_observer(on_channel_cancelled);
+    }
+}
+
+fn send_channel_progress(
+    q_channeling: Query<(Entity, &Channeling), With<Player>>,
+    mut commands: Commands,
+) {
+    for (player_e, channeling) in &q_channeling {
+        commands.server_trigger(ToClients {
+            mode: SendMode::Direct(player_e),
+            event: ChannelProgress {
+                action: channeling.action,
+                progress: channeling.progress(),
+            },
+        });
+    }
+}
+
+fn on_channel_completed(
+    trigger: Trigger<ChannelCompleted>,
+    q_player: Query<(), With<Player>>,
+    mut commands: Commands,
+) {
+    let player_e = trigger.target();
+    if q_player.contains(player_e) {
+        commands.server_trigger(ToClients {
+            mode: SendMode::Direct(player_e),
+            event: ChannelEnded {
+                action: trigger.event().action,
+                cancelled: None,
+            },
+        });
+    }
+}
+
+fn on_channel_cancelled(
+    trigger: Trigger<ChannelCancelled>,
+    q_player: Query<(), With<Player>>,
+    mut commands: Commands,
+) {
+    let player_e = trigger.target();
+    let event = trigger.event();
+    debug!(
+        "{:?} of {} cancelled: {:?}",
+        event.action, player_e, event.reason
+    );
+    if q_player.contains(player_e) {
+        commands.server_trigger(ToClients {
+            mode: SendMode::Direct(player_e),
+            event: ChannelEnded {
+                action: event.action,
+                cancelled: Some(event.reason),
+            },
+        });
+    }
+}
@@ -25,7 +25,7 @@ fn heal_player(
     let player_e = trigger.target();
     if let Ok(mut health) = healths.get_mut(player_e) {
         if health.is_dead() {
-            health.heal(80.0);
+            health.heal(CLONE_HEALTH_80);
         }
     }
 }
@@ -0,0 +1,102 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+
+/// Resolves the shots players fire, the server decides what they hit and how much it hurts.
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ActionPlugin)
+            .add_systems(FixedUpdate, tick_fire_cooldown)
+            .add_observer(on_fire_request);
+    }
+}
+
+/// Layers a shot hits, structures stop it before it reaches players behind them.
+const SHOT_LAYERS: [GameLayer; 2] = [GameLayer::Player, GameLayer::Structure];
+
+/// Weapon is reloading, further shots are ignored until it is removed.
+#[derive(Component, Deref, DerefMut)]
+struct FireCooldown(Timer);
+
+fn tick_fire_cooldown(
+    mut q_cooldown: Query<(Entity, &mut FireCooldown)>,
+    r_time: Res<Time<Fixed>>,
+    mut commands: Commands,
+) {
+    for (player_e, mut cooldown) in &mut q_cooldown {
+        cooldown.tick(r_time.delta());
+        if cooldown.finished() {
+            commands.entity(player_e).remove::<FireCooldown>();
+        }
+    }
+}
+
+fn on_fire_request(
+    trigger: Trigger<FromClient<FireRequest>>,
+    q_shooter: Query<
+        &Transform,
+        (
+            With<Player>,
+            Without<Dead>,
+            Without<Immobilized>,
+            Without<FireCooldown>,
+        ),
+    >,
+    q_victim: Query<Has<SpawnProtection>, (With<Player>, Without<Dead>)>,
+    q_spatial: SpatialQuery,
+    mut commands: Commands,
+) {
+    let shooter_e = trigger.client_entity;
+    let Ok(shooter_tr) = q_shooter.get(shooter_e) else {
+        return;
+    };
+    let Some(direction) = aim_direction(trigger.event().event.direction) else {
+        warn!("Client {} fired without aiming", shooter_e);
+        return;
+    };
+    commands
+        .entity(shooter_e)
+        .insert(FireCooldown(Timer::new(FIRE_COOLDOWN, TimerMode::Once)));
+
+    let hit = q_spatial.cast_ray(
+        shooter_tr.translation,
+        direction,
+        WEAPON_RANGE,
+        true,
+        &SpatialQueryFilter::from_mask(SHOT_LAYERS).with_excluded_entities([shooter_e]),
+    );
+    let Some((victim_e, protected)) =
+        hit.and_then(|hit| Some((hit.entity, q_victim.get(hit.entity).ok()?)))
+    else {
+        return;
+    };
+
+    if protected {
+        info!(
+            "Player {} hit {} but spawn protection blocked it",
+            shooter_e, victim_e
+        );
+    } else {
+        info!("Player {} hit {}", shooter_e, victim_e);
+        let (min, max) = WEAPON_DAMAGE;
+        commands.trigger(DamageActionEvent {
+            receiver: victim_e,
+            range: Range::new(min, max),
+            attacker: Some(shooter_e),
+        });
+    }
+    let event = HitConfirmed {
+        shooter: shooter_e,
+        victim: victim_e,
+        blocked: protected,
+    };
+    for client_e in [shooter_e, victim_e] {
+        commands.server_trigger(ToClients {
+            mode: SendMode::Direct(client_e),
+            event,
+        });
+    }
+}
@@ -1,5 +1,6 @@
+use crate::server::TransferPlayer;
 use bevy::prelude::*;
-use bevy_replicon::prelude::{FromClient, SendMode, ServerTriggerExt, ToClients};
+use bevy_replicon::prelude::FromClient;
 use corp_shared::prelude::*;
 
 #[derive(Component, Deref, DerefMut)]
@@ -24,22 +25,26 @@ fn despawn_dead_player(
     for (dead_player_e, mut death_timer) in &mut q_death_timer {
         death_timer.tick(r_time.delta());
         if death_timer.finished() {
-            info!(
-                "Sending SendDeadPlayerToCloningCommand entity {:?}",
-                dead_player_e
-            );
-            commands.server_trigger(ToClients {
-                mode: SendMode::Direct(dead_player_e),
-                event: SendDeadPlayerToCloningCommand,
-            });
-            commands.entity(dead_player_e).remove::<DeathTimer>();
+            info!("Sending dead player {:?} to cloning", dead_player_e);
+            commands
+                .entity(dead_player_e)
+                .remove::<DeathTimer>()
+                .trigger(TransferPlayer::to(Colony::Cloning));
         }
     }
 }
 
-fn on_add_dead(trigger: Trigger<OnAdd, Dead>, mut commands: Commands) {
+fn on_add_dead(
+    trigger: Trigger<OnAdd, Dead>,
+    q_last_attacker: Query<&LastAttacker>,
+    mut commands: Commands,
+) {
     let player_e = trigger.target();
     info!("On add Dead for entity {:?} triggered", player_e);
+    if let Ok(LastAttacker(killer_e)) = q_last_attacker.get(player_e) {
+        info!("Player {} killed by {}", player_e, killer_e);
+        commands.entity(player_e).insert(KilledBy(*killer_e));
+    }
     commands
         .entity(player_e)
         .insert(Immobilized)
@@ -1,52 +1,42 @@
+use crate::server::*;
 use bevy::prelude::*;
 use bevy_replicon::prelude::*;
 use corp_shared::prelude::*;
 use std::time::Duration;
 
+/// Simulates the doors of the colony scene, clients see the replicated [`DoorState`].
 pub struct DoorPlugin;
 
 impl Plugin for DoorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(on_hack_door_command);
+        app.add_systems(
+            FixedUpdate,
+            (
+                door_cooldown_system,
+                process_temporary_faction_ownership_timers_system,
+            )
+                .chain(),
+        )
+        .add_observer(replicate_scene_structure::<Door>)
+        .add_observer(replicate_scene_structure::<DoorTerminal>)
+        .add_observer(on_hack_door_command)
+        .add_observer(on_door_hack_completed)
+        .add_observer(on_door_hack_cancelled);
     }
 }
 
+/// Starts hacking the door, the hacking tool burns out even if the hack gets interrupted.
 fn on_hack_door_command(
-    trigger: Trigger<FromClient<DoorHackCommand>>,
+    trigger: Trigger<DoorHackCommand>,
     mut commands: Commands,
     contains_query: Query<&Contains, With<Inventory>>,
     hacking_tools: Query<&HackingTool>,
-    mut doors: Query<(&mut DoorState, &mut OwnershipRegistry)>,
-    faction_info_query: Query<&PlayerFactionInfo, With<Player>>,
+    doors: Query<(), (With<DoorState>, With<OwnershipRegistry>)>,
+    q_hacker: Query<(), (With<PlayerFactionInfo>, Without<Channeling>)>,
 ) {
     let door_e = trigger.target();
-    let from_client = trigger.event();
-    let client_e = from_client.client_entity;
-    if let Ok(inventory_content) = contains_query.get(client_e) {
-        if let Some(hacking_tool_e) = inventory_content
-            .into_iter()
-            .find(|item| hacking_tools.get(**item).is_ok())
-        {
-            if let Ok((mut door_state, mut door_owners)) = doors.get_mut(door_e) {
-                if let Ok(faction_info) = faction_info_query.get(client_e) {
-                    commands.entity(*hacking_tool_e).despawn();
-                    door_owners.add(Ownership::Hacked(
-                        faction_info.faction,
-                        Timer::new(
-                            Duration::from_secs_f32(DoorState::HACK_DURATION_SECS),
-                            TimerMode::Once,
-                        ),
-                    ));
-                    door_state.toggle();
-                    let to_clients = ToClients {
-                        mode: SendMode::Broadcast,
-                        event: DoorHackedEvent::Successful,
-                    };
-                    commands.server_trigger_targets(to_clients, door_e);
-                }
-            }
-        }
-    } else {
+    let client_e = trigger.event().user;
+    let Ok(inventory_content) = contains_query.get(client_e) else {
         warn!(
             "Client {} tried to hack a door without a inventory",
             client_e
@@ -56,5 +46,69 @@ fn on_hack_door_command(
             event: DoorHackedEvent::Failure,
         };
         commands.server_trigger_targets(to_clients, door_e);
+        return;
+    };
+    let Some(hacking_tool_e) = inventory_content
+        .into_iter()
+        .find(|item| hacking_tools.get(**item).is_ok())
+    else {
+        return;
+    };
+    if doors.contains(door_e) && q_hacker.contains(client_e) {
+        commands.entity(*hacking_tool_e).despawn();
+        commands.entity(client_e).insert(Channeling::new(
+            ChannelAction::HackDoor,
+            door_e,
+            Duration::from_secs_f32(DoorState::HACK_CHANNEL_SECS),
+        ));
+    }
+}
+
+fn on_door_hack_completed(
+    trigger: Trigger<ChannelCompleted>,
+    mut commands: Commands,
+    mut doors: Query<(&mut DoorState, &mut OwnershipRegistry)>,
+    faction_info_query: Query<&PlayerFactionInfo, With<Player>>,
+) {
+    let ChannelCompleted { action, target } = *trigger.event();
+    if action != ChannelAction::HackDoor {
+        return;
+    }
+    let (Ok((mut door_state, mut door_owners)), Ok(faction_info)) = (
+        doors.get_mut(target),
+        faction_info_query.get(trigger.target()),
+    ) else {
+        return;
+    };
+    door_owners.add(Ownership::Hacked(
+        faction_info.faction,
+        Timer::new(
+            Duration::from_secs_f32(DoorState::HACK_DURATION_SECS),
+            TimerMode::Once,
+        ),
+    ));
+    door_state.toggle();
+    let to_clients = ToClients {
+        mode: SendMode::Broadcast,
+        event: DoorHackedEvent::Successful,
+    };
+    commands.server_trigger_targets(to_clients, target);
+    commands.trigger_targets(
+        AwardExperience {
+            source: ExperienceSource::Hack,
+        },
+        trigger.target(),
+    );
+}
+
+fn on_door_hack_cancelled(trigger: Trigger<ChannelCancelled>, mut commands: Commands) {
+    let ChannelCancelled { action, target, .. } = *trigger.event();
+    if action != ChannelAction::HackDoor {
+        return;
     }
+    let to_clients = ToClients {
+        mode: SendMode::Broadcast,
+        event: DoorHackedEvent::Failure,
+    };
+    commands.server_trigger_targets(to_clients, target);
 }
@@ -10,12 +10,18 @@ impl Plugin for HealthRemotePlugin {
 }
 
 fn update_player_health(
-    changed_player_health: Query<(Entity, &Health), (With<Player>, Changed<Health>)>,
+    changed_player_health: Query<
+        (Entity, &Health, Option<&CreatureName>),
+        (With<Player>, Changed<Health>),
+    >,
     mut commands: Commands,
 ) {
-    for (player_e, health) in &changed_player_health {
+    for (player_e, health, name) in &changed_player_health {
         if health.is_dead() {
-            info!("Player {} is dead", player_e);
+            match name {
+                Some(name) => info!("Player {} {} is dead", name, player_e),
+                None => info!("Player {} is dead", player_e),
+            }
             commands.entity(player_e).insert(Dead);
         }
     }
@@ -0,0 +1,266 @@
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    time::common_conditions::on_timer,
+};
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+use std::time::Duration;
+
+const INTEREST_CELL_SIZE: f32 = 16.0;
+const INTEREST_UPDATE_SECS: f32 = 0.2;
+
+/// Distance at which a replicated entity becomes visible to a client.
+///
+/// Entities enter the view of a client at `enter` and leave it only after moving past `exit`,
+/// so entities standing on the border don't flicker in and out.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct InterestRadius {
+    pub enter: f32,
+    pub exit: f32,
+}
+
+impl InterestRadius {
+    pub fn new(enter: f32, hysteresis: f32) -> Self {
+        Self {
+            enter,
+            exit: enter + hysteresis,
+        }
+    }
+}
+
+/// Replicated entities currently visible to a client.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct ClientInterest(HashSet<Entity>);
+
+/// Entities with an [`InterestRadius`] bucketed by their position on the XZ plane, with the
+/// [`InterestZone`] each of them is in.
+#[derive(Resource, Default)]
+struct InterestGrid {
+    cells: HashMap<IVec2, Vec<Entity>>,
+    zones: HashMap<Entity, Entity>,
+    max_exit: f32,
+}
+
+impl InterestGrid {
+    fn cell(position: Vec3) -> IVec2 {
+        (position.xz() / INTEREST_CELL_SIZE).floor().as_ivec2()
+    }
+
+    fn clear(&mut self) {
+        self.cells.values_mut().for_each(Vec::clear);
+        self.zones.clear();
+        self.max_exit = 0.0;
+    }
+
+    fn insert(
+        &mut self,
+        entity: Entity,
+        position: Vec3,
+        radius: &InterestRadius,
+        zone: Option<Entity>,
+    ) {
+        self.cells
+            .entry(Self::cell(position))
+            .or_default()
+            .push(entity);
+        if let Some(zone) = zone {
+            self.zones.insert(entity, zone);
+        }
+        self.max_exit = self.max_exit.max(radius.exit);
+    }
+
+    /// Entities in a zone are only seen from the same zone, entities outside of any zone are
+    /// seen from everywhere.
+    fn sees_into_zone(&self, client: Entity, entity: Entity) -> bool {
+        match self.zones.get(&entity) {
+            Some(zone) => self.zones.get(&client) == Some(zone),
+            None => true,
+        }
+    }
+
+    fn nearby(&self, position: Vec3) -> impl Iterator<Item = Entity> + '_ {
+        let center = Self::cell(position);
+        let reach = (self.max_exit / INTEREST_CELL_SIZE).ceil() as i32;
+        (-reach..=reach)
+            .flat_map(move |x| (-reach..=reach).map(move |y| center + IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+}
+
+pub trait AppInterestExt {
+    /// Replicates entities with component `C` only to clients whose player is within `enter`
+    /// distance, and keeps them visible until the player moves `hysteresis` further away.
+    fn interest_radius<C: Component>(&mut self, enter: f32, hysteresis: f32) -> &mut Self;
+}
+
+impl AppInterestExt for App {
+    fn interest_radius<C: Component>(&mut self, enter: f32, hysteresis: f32) -> &mut Self {
+        let radius = InterestRadius::new(enter, hysteresis);
+        self.add_observer(move |trigger: Trigger<OnAdd, C>, mut commands: Commands| {
+            commands.entity(trigger.target()).insert(radius);
+        })
+    }
+}
+
+pub struct InterestPlugin;
+
+impl Plugin for InterestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InterestGrid>()
+            .interest_radius::<Player>(60.0, 10.0)
+            .interest_radius::<Backpack>(30.0, 5.0)
+            .add_systems(
+                FixedUpdate,
+                (rebuild_interest_grid, update_client_interest)
+                    .chain()
+                    .run_if(on_timer(Duration::from_secs_f32(INTEREST_UPDATE_SECS))),
+            )
+            .add_observer(init_client_interest);
+    }
+}
+
+fn init_client_interest(trigger: Trigger<OnAdd, AuthorizedClient>, mut commands: Commands) {
+    commands
+        .entity(trigger.target())
+        .insert(ClientInterest::default());
+}
+
+fn rebuild_interest_grid(
+    mut grid: ResMut<InterestGrid>,
+    q_interest: Query<(Entity, &Transform, &InterestRadius), With<Replicated>>,
+    q_zones: Query<(Entity, &Transform, &InterestZone)>,
+) {
+    grid.clear();
+    for (entity, transform, radius) in &q_interest {
+        let position = transform.translation;
+        let zone = q_zones
+            .iter()
+            .find(|(_, zone_transform, zone)| zone.contains(zone_transform, position))
+            .map(|(zone_e, ..)| zone_e);
+        grid.insert(entity, position, radius, zone);
+    }
+}
+
+fn update_client_interest(
+    grid: Res<InterestGrid>,
+    mut q_clients: Query<(
+        Entity,
+        &mut ClientVisibility,
+        &mut ClientInterest,
+        Option<&Transform>,
+    )>,
+    q_interest: Query<(&Transform, &InterestRadius), With<Replicated>>,
+    q_global: Query<Entity, (With<Replicated>, Without<InterestRadius>, Without<StoredIn>)>,
+    q_contains: Query<&Contains>,
+) {
+    for (client_entity, mut visibility, mut interest, client_transform) in &mut q_clients {
+        let mut visible: HashSet<Entity> = q_global.iter().collect();
+
+        if let Some(client_transform) = client_transform {
+            let client_position = client_transform.translation;
+            for entity in grid.nearby(client_position) {
+                let Ok((transform, radius)) = q_interest.get(entity) else {
+                    continue;
+                };
+                if !grid.sees_into_zone(client_entity, entity) {
+                    continue;
+                }
+                let range = if interest.contains(&entity) {
+                    radius.exit
+                } else {
+                    radius.enter
+                };
+                let distance = client_position.xz().distance(transform.translation.xz());
+                if distance <= range {
+                    visible.insert(entity);
+                }
+            }
+        }
+
+        // Stored items follow the visibility of their container
+        let containers = visible.iter().copied().collect::<Vec<_>>();
+        for container in containers {
+            if let Ok(contains) = q_contains.get(container) {
+                visible.extend(contains.into_iter().copied());
+            }
+        }
+
+        for entity in interest.difference(&visible) {
+            visibility.set_visibility(*entity, false);
+        }
+        for entity in visible.difference(&interest) {
+            visibility.set_visibility(*entity, true);
+        }
+        **interest = visible;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zones_hide_entities_within_radius() {
+        // given
+        let mut app = App::new();
+        app.init_resource::<InterestGrid>()
+            .add_systems(Update, rebuild_interest_grid);
+        app.world_mut().spawn((
+            Transform::from_xyz(10.0, 0.0, 0.0),
+            InterestZone {
+                half_extents: Vec3::splat(5.0),
+            },
+        ));
+        let radius = InterestRadius::new(60.0, 10.0);
+        let inside_e = spawn_interest(&mut app, Vec3::new(8.0, 0.0, 0.0), radius);
+        let inside_too_e = spawn_interest(&mut app, Vec3::new(12.0, 0.0, 2.0), radius);
+        let outside_e = spawn_interest(&mut app, Vec3::new(2.0, 0.0, 0.0), radius);
+
+        // when
+        app.update();
+
+        // then
+        let grid = app.world().resource::<InterestGrid>();
+        assert!(grid.sees_into_zone(inside_e, inside_too_e));
+        assert!(grid.sees_into_zone(inside_e, outside_e));
+        assert!(!grid.sees_into_zone(outside_e, inside_e));
+        let nearby = grid.nearby(Vec3::ZERO).collect::<Vec<_>>();
+        assert!(nearby.contains(&inside_e) && nearby.contains(&outside_e));
+    }
+
+    #[test]
+    fn entities_in_different_zones_are_hidden() {
+        // given
+        let mut app = App::new();
+        app.init_resource::<InterestGrid>()
+            .add_systems(Update, rebuild_interest_grid);
+        for x in [0.0, 20.0] {
+            app.world_mut().spawn((
+                Transform::from_xyz(x, 0.0, 0.0),
+                InterestZone {
+                    half_extents: Vec3::splat(5.0),
+                },
+            ));
+        }
+        let radius = InterestRadius::new(60.0, 10.0);
+        let first_e = spawn_interest(&mut app, Vec3::new(1.0, 0.0, 0.0), radius);
+        let second_e = spawn_interest(&mut app, Vec3::new(19.0, 0.0, 0.0), radius);
+
+        // when
+        app.update();
+
+        // then
+        let grid = app.world().resource::<InterestGrid>();
+        assert!(!grid.sees_into_zone(first_e, second_e));
+        assert!(!grid.sees_into_zone(second_e, first_e));
+    }
+
+    fn spawn_interest(app: &mut App, position: Vec3, radius: InterestRadius) -> Entity {
+        app.world_mut()
+            .spawn((Transform::from_translation(position), radius, Replicated))
+            .id()
+    }
+}
@@ -0,0 +1,151 @@
+use crate::server::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+
+/// Keeps containers within their slots and weight, whatever moved the item.
+pub struct InventoryRemotePlugin;
+
+impl Plugin for InventoryRemotePlugin {
+    fn build(&self, app: &mut App) {
+        // Loaded when the server starts, a broken catalog stops it before any colony runs
+        let catalog = app
+            .world()
+            .resource::<GameServerConfig>()
+            .item_catalog
+            .clone();
+        app.insert_resource(catalog)
+            .add_plugins((ItemCatalogPlugin, InventoryPlugin))
+            .init_resource::<PreviousContainers>()
+            .add_observer(remember_previous_container)
+            .add_observer(forget_previous_container)
+            .add_observer(validate_stored_in)
+            .add_observer(on_split_stack_request)
+            .add_observer(on_merge_stack_request)
+            .add_observer(send_inventory_rejected);
+    }
+}
+
+/// Containers items are being moved out of, to move them back if the new one is full.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct PreviousContainers(HashMap<Entity, Entity>);
+
+fn remember_previous_container(
+    trigger: Trigger<OnReplace, StoredIn>,
+    q_stored_in: Query<&StoredIn>,
+    mut previous: ResMut<PreviousContainers>,
+) {
+    let item_e = trigger.target();
+    if let Ok(stored_in) = q_stored_in.get(item_e) {
+        previous.insert(item_e, stored_in.container());
+    }
+}
+
+fn forget_previous_container(
+    trigger: Trigger<OnRemove, StoredIn>,
+    mut previous: ResMut<PreviousContainers>,
+) {
+    previous.remove(&trigger.target());
+}
+
+fn validate_stored_in(
+    trigger: Trigger<OnInsert, StoredIn>,
+    q_stored_in: Query<&StoredIn>,
+    q_inventory: Query<(&Inventory, &Contains)>,
+    q_item: Query<(Option<&ItemId>, &Quantity)>,
+    catalog: Res<ItemCatalog>,
+    mut previous: ResMut<PreviousContainers>,
+    mut commands: Commands,
+) {
+    let item_e = trigger.target();
+    let previous_e = previous.remove(&item_e);
+    let Ok(stored_in) = q_stored_in.get(item_e) else {
+        return;
+    };
+    let container_e = stored_in.container();
+    let Ok((inventory, contains)) = q_inventory.get(container_e) else {
+        return;
+    };
+    let load = InventoryLoad::of(
+        contains
+            .iter()
+            .filter_map(|stack_e| q_item.get(*stack_e).ok()),
+        &catalog,
+    );
+    if inventory.fits(load) {
+        return;
+    }
+
+    match previous_e.filter(|previous_e| q_inventory.contains(*previous_e)) {
+        Some(previous_e) => {
+            warn!(
+                "Container {} is over capacity, moving {} back to {}",
+                container_e, item_e, previous_e
+            );
+            commands.entity(item_e).insert(StoredIn(previous_e));
+        }
+        None => warn!("Container {} is over capacity with {}", container_e, item_e),
+    }
+}
+
+fn is_in_inventory(q_stored_in: &Query<&StoredIn>, item_e: Entity, client_e: Entity) -> bool {
+    q_stored_in
+        .get(item_e)
+        .is_ok_and(|stored_in| stored_in.container() == client_e)
+}
+
+fn on_split_stack_request(
+    trigger: Trigger<FromClient<SplitStackRequest>>,
+    q_stored_in: Query<&StoredIn>,
+    mut commands: Commands,
+) {
+    let client_e = trigger.client_entity;
+    let request = trigger.event().event;
+    if !is_in_inventory(&q_stored_in, request.item, client_e) {
+        warn!(
+            "Client {} tried to split {} which it doesn't carry",
+            client_e, request.item
+        );
+        return;
+    }
+    commands.trigger_targets(
+        SplitStack {
+            amount: request.amount,
+        },
+        request.item,
+    );
+}
+
+fn on_merge_stack_request(
+    trigger: Trigger<FromClient<MergeStackRequest>>,
+    q_stored_in: Query<&StoredIn>,
+    mut commands: Commands,
+) {
+    let client_e = trigger.client_entity;
+    let request = trigger.event().event;
+    if !is_in_inventory(&q_stored_in, request.item, client_e)
+        || !is_in_inventory(&q_stored_in, request.into, client_e)
+    {
+        warn!(
+            "Client {} tried to merge {} into {} which it doesn't carry",
+            client_e, request.item, request.into
+        );
+        return;
+    }
+    commands.trigger_targets(MergeStack { into: request.into }, request.item);
+}
+
+/// Players hear about items their inventory has no room for, containers in the world don't.
+fn send_inventory_rejected(
+    trigger: Trigger<InventoryRejected>,
+    q_player: Query<(), With<Player>>,
+    mut commands: Commands,
+) {
+    let event = *trigger.event();
+    if q_player.contains(event.container) {
+        commands.server_trigger(ToClients {
+            mode: SendMode::Direct(event.container),
+            event,
+        });
+    }
+}
@@ -7,6 +7,10 @@ use std::time::Duration;
 
 pub struct LootPlugin;
 
+/// Loot action a player is channeling, carried out once the channel completes.
+#[derive(Component, Debug)]
+struct PendingLoot(LootAction);
+
 impl Plugin for LootPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
@@ -17,90 +21,167 @@ impl Plugin for LootPlugin {
             )
                 .chain(),
         )
+        .add_observer(on_loot_request)
+        .add_observer(on_loot_channel_completed)
+        .add_observer(on_loot_channel_cancelled)
+        .add_observer(send_loot_contents)
+        .add_observer(send_loot_result)
         .add_observer(on_add_dead_drop_loot);
     }
 }
 
-pub fn despawn_empty_backpack(
-    mut commands: Commands,
-    q_entity_backpack: Query<Entity, (Without<Contains>, With<Backpack>)>,
-) {
-    for e_bag in &q_entity_backpack {
-        info!("Despawning empty backpack {:?}", e_bag);
-        commands.entity(e_bag).try_despawn();
-    }
-}
-
 fn loot_spawner(
     mut commands: Commands,
     q_backpacks: Query<&Backpack>,
+    catalog: Res<ItemCatalog>,
     mut rng: GlobalEntropy<WyRand>,
 ) {
     const MAX_BACKPACKS: usize = 10;
-    if q_backpacks.iter().count() > MAX_BACKPACKS {
+    if q_backpacks.iter().count() > MAX_BACKPACKS || catalog.is_empty() {
         return;
     }
 
     let x: f32 = rng.gen_range(-10.0..=10.0);
     let z: f32 = rng.gen_range(-10.0..=10.0);
+    let mut items = catalog.items();
+    let Some(item) = items.nth(rng.gen_range(0..items.len())) else {
+        return;
+    };
+    let item_id = ItemId::new(item.id.clone());
 
     commands
         .spawn((
             Backpack,
             Transform::from_xyz(x, 0.1, z),
             Replicated,
-            related!(Contains[(HackingTool, Replicated)]),
+            related!(Contains[(item_id, Replicated)]),
         ))
+        .observe(on_use_backpack)
         .observe(on_loot_command);
 }
 
-fn on_loot_command(
+/// Loots on behalf of the requesting client, whoever the request claims to be the user.
+///
+/// Listing is instant, taking items is channeled.
+fn on_loot_request(
     trigger: Trigger<FromClient<LootCommand>>,
-    loot_bag_query: Query<&Contains, With<Backpack>>,
+    q_backpack: Query<(), With<Backpack>>,
+    q_looter: Query<(), (With<Player>, Without<Channeling>)>,
     mut commands: Commands,
-) -> Result {
-    let player_e = trigger.event().client_entity;
+) {
     let backpack_e = trigger.target();
-    let backpack_action = trigger.event().event.action;
-    info!("Backpack action: {:?}", backpack_action);
-    let backpack_content = loot_bag_query.get(backpack_e)?;
-    info!("Backpack {:?} content: {:?}", backpack_e, backpack_content);
-
-    match backpack_action {
-        LootAction::TakeAll => {
-            for item_entity in backpack_content.into_iter() {
-                commands.entity(*item_entity).insert(StoredIn(player_e));
-                info!(
-                    "Transferring item {} to inventory {}",
-                    item_entity, player_e
-                );
-            }
+    let client_e = trigger.client_entity;
+    if !q_backpack.contains(backpack_e) {
+        warn!(
+            "Client {} tried to loot {} which is not a backpack",
+            client_e, backpack_e
+        );
+        return;
+    }
+    match trigger.event().event.action {
+        LootAction::List => {
+            commands.trigger_targets(
+                LootCommand {
+                    user: client_e,
+                    action: LootAction::List,
+                },
+                backpack_e,
+            );
         }
-        action => {
-            warn!("not implemented: {:?}", action);
+        action if q_looter.contains(client_e) => {
+            start_loot(&mut commands, client_e, backpack_e, action);
         }
+        _ => debug!("Client {} is busy, not looting {}", client_e, backpack_e),
     }
-    Ok(())
+}
+
+fn on_use_backpack(
+    trigger: Trigger<UseCommand>,
+    q_looter: Query<(), (With<Player>, Without<Channeling>)>,
+    mut commands: Commands,
+) {
+    let user = trigger.event().user;
+    if q_looter.contains(user) {
+        start_loot(&mut commands, user, trigger.target(), LootAction::TakeAll);
+    }
+}
+
+fn start_loot(commands: &mut Commands, user: Entity, backpack_e: Entity, action: LootAction) {
+    commands.entity(user).insert((
+        PendingLoot(action),
+        Channeling::new(
+            ChannelAction::Loot,
+            backpack_e,
+            Duration::from_secs_f32(LOOT_CHANNEL_SECS),
+        ),
+    ));
+}
+
+fn on_loot_channel_completed(
+    trigger: Trigger<ChannelCompleted>,
+    q_pending: Query<&PendingLoot>,
+    q_backpack: Query<(), With<Backpack>>,
+    mut commands: Commands,
+) {
+    let ChannelCompleted { action, target } = *trigger.event();
+    let user = trigger.target();
+    if action != ChannelAction::Loot {
+        return;
+    }
+    let Ok(&PendingLoot(action)) = q_pending.get(user) else {
+        return;
+    };
+    commands.entity(user).remove::<PendingLoot>();
+    if q_backpack.contains(target) {
+        commands.trigger_targets(LootCommand { user, action }, target);
+    }
+}
+
+fn on_loot_channel_cancelled(trigger: Trigger<ChannelCancelled>, mut commands: Commands) {
+    if trigger.event().action == ChannelAction::Loot {
+        commands.entity(trigger.target()).remove::<PendingLoot>();
+    }
+}
+
+fn send_loot_contents(trigger: Trigger<LootContents>, mut commands: Commands) {
+    let player_e = trigger.target();
+    commands.server_trigger(ToClients {
+        mode: SendMode::Direct(player_e),
+        event: trigger.event().clone(),
+    });
+}
+
+fn send_loot_result(trigger: Trigger<LootResult>, mut commands: Commands) {
+    let player_e = trigger.target();
+    commands.server_trigger(ToClients {
+        mode: SendMode::Direct(player_e),
+        event: *trigger.event(),
+    });
 }
 
 fn on_add_dead_drop_loot(
     trigger: Trigger<OnAdd, Dead>,
     mut commands: Commands,
     q_player: Query<&Transform, With<Player>>,
-    contains_query: Query<&Contains, With<Inventory>>,
+    contains_query: Query<(&Contains, &Inventory)>,
 ) -> Result {
     let dead_player_entity = trigger.target();
     let dead_player_transform = q_player.get(dead_player_entity)?;
 
-    if let Ok(dead_player_loot) = contains_query.get(dead_player_entity) {
+    if let Ok((dead_player_loot, inventory)) = contains_query.get(dead_player_entity) {
+        // The bag holds as much as the player did, so everything gets dropped
         let drop_loot_bag = commands
-            .spawn((Backpack, Replicated, *dead_player_transform))
+            .spawn((Backpack, *inventory, Replicated, *dead_player_transform))
+            .observe(on_use_backpack)
             .observe(on_loot_command)
             .id();
         for item_entity in dead_player_loot.into_iter() {
-            commands
-                .entity(*item_entity)
-                .insert(StoredIn(drop_loot_bag));
+            commands.trigger_targets(
+                TransferItem {
+                    container: drop_loot_bag,
+                },
+                *item_entity,
+            );
         }
     }
 
@@ -1,15 +1,44 @@
+mod channel;
 mod cloning;
+mod combat;
 mod death;
 mod door;
 mod health;
+mod interest;
+mod inventory;
 mod loot;
+mod movement;
 mod players;
+mod progression;
+mod scene;
 mod server;
+mod sessions;
+mod spawn;
+mod telemetry;
+mod territory;
+mod transfer;
+mod usable;
+mod vortex;
 
-pub use crate::{config::*, token::*};
+pub use crate::{character::*, config::*, control::*, handoff::*, session::*, ticket::*, token::*};
+pub use channel::*;
 pub use cloning::*;
+pub use combat::*;
 pub use death::*;
+pub use door::*;
 pub use health::*;
+pub use interest::*;
+pub use inventory::*;
 pub use loot::*;
+pub use movement::*;
 pub use players::*;
+pub use progression::*;
+pub use scene::*;
 pub use server::*;
+pub use sessions::*;
+pub use spawn::*;
+pub use telemetry::*;
+pub use territory::*;
+pub use transfer::*;
+pub use usable::*;
+pub use vortex::*;
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+use std::time::Duration;
+
+/// Moves players to the positions their clients report.
+///
+/// Movement is simulated by the client that controls the player, the server takes the reported
+/// transform as long as it is reachable at [`PLAYER_MAX_SPEED`] since the previous command, and
+/// clamps it otherwise. The server needs the positions to pick what each client gets to see.
+pub struct PlayerMovementPlugin;
+
+impl Plugin for PlayerMovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(init_last_move).add_observer(move_clients);
+    }
+}
+
+/// Longest gap between move commands that still earns movement, so idling doesn't bank distance.
+const MAX_MOVE_INTERVAL: Duration = Duration::from_millis(500);
+/// Slack on top of the speed limit for timing jitter between client and server.
+const MOVE_TOLERANCE: f32 = 0.5;
+
+/// Last move command applied to a player.
+#[derive(Component)]
+struct LastMove {
+    sequence: u32,
+    received: Duration,
+}
+
+fn init_last_move(trigger: Trigger<OnAdd, Player>, time: Res<Time>, mut commands: Commands) {
+    commands.entity(trigger.target()).insert(LastMove {
+        sequence: 0,
+        received: time.elapsed(),
+    });
+}
+
+fn move_clients(
+    trigger: Trigger<FromClient<PlayerMoveClientCommand>>,
+    time: Res<Time>,
+    mut q_player: Query<(&mut Transform, &mut LastMove), (With<Player>, Without<Immobilized>)>,
+) {
+    let Ok((mut transform, mut last_move)) = q_player.get_mut(trigger.client_entity) else {
+        return;
+    };
+    let command = &trigger.event().event;
+    // Unreliable channel, late or reordered commands would move the player backwards
+    if command.sequence <= last_move.sequence || !command.translation.is_finite() {
+        return;
+    }
+    let Some(rotation) = Vec4::from(command.rotation).try_normalize() else {
+        return;
+    };
+    let elapsed = (time.elapsed() - last_move.received).min(MAX_MOVE_INTERVAL);
+    let max_distance = PLAYER_MAX_SPEED * elapsed.as_secs_f32() + MOVE_TOLERANCE;
+    let offset = command.translation - transform.translation;
+    transform.translation += offset.clamp_length_max(max_distance);
+    transform.rotation = Quat::from_vec4(rotation);
+    last_move.sequence = command.sequence;
+    last_move.received = time.elapsed();
+}
@@ -1,19 +1,56 @@
-use bevy::prelude::*;
+use crate::server::*;
+use aeronet::io::connection::Disconnected;
+use avian3d::prelude::*;
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, IoTaskPool, Task},
+    time::common_conditions::on_timer,
+};
+use bevy_rand::prelude::{GlobalEntropy, WyRand};
 use bevy_replicon::prelude::*;
-use corp_shared::prelude::*;
+use corp_shared::{prelude::*, CLONING_SPAWN_POSITION};
+use kameo::actor::ActorRef;
+use rand::Rng;
+use std::time::{Duration, Instant};
 
 pub struct PlayersPlugin;
 
 impl Plugin for PlayersPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(init_clients)
+        app.add_systems(PreUpdate, (start_player_claims, spawn_players).chain())
+            .add_systems(
+                Update,
+                save_players.run_if(on_timer(Duration::from_secs_f32(SAVE_INTERVAL_SECS))),
+            )
+            .add_observer(init_clients)
+            .add_observer(deposit_players)
             .add_observer(connect_clients)
             .add_observer(despawn_clients);
     }
 }
 
+const HANDOFF_RETRY: Duration = Duration::from_millis(100);
+const HANDOFF_TIMEOUT: Duration = Duration::from_secs(3);
+const SAVE_INTERVAL_SECS: f32 = 30.0;
+const STARTING_FACTIONS: [Faction; 3] = [Faction::EC, Faction::CMG, Faction::VI];
+/// Player waiting for its state to be claimed from the [`Handoff`], the claim starts on the next
+/// `PreUpdate`.
+#[derive(Component)]
+struct PendingSpawn(Option<Task<PlayerClaim>>);
+
+/// Outcome of claiming the player of a session.
+enum PlayerClaim {
+    /// Custody moved to the session, with the state to spawn the player from.
+    Arrived(Option<PlayerSnapshot>),
+    /// The session already holds the player, there is nothing to load or spawn.
+    Held,
+}
+
+/// Spawns the player of a session once, a repeated command would load the saved character again
+/// and hand out its items a second time.
 fn init_clients(
     trigger: Trigger<FromClient<PlayerSpawnClientCommand>>,
+    q_client: Query<(Has<Player>, Has<PendingSpawn>), With<UserId>>,
     mut commands: Commands,
 ) -> Result {
     info!(
@@ -22,30 +59,282 @@ fn init_clients(
     );
 
     let client_entity = trigger.client_entity;
-
-    // Create player
-    commands.entity(client_entity).insert((
-        Player,
-        Replicated,
-        Transform::default(),
-        Health::default(),
-        // Health::from(response.hit_points),
-        // CreatureName(response.character_name),
-        AuthorizedClient,
-        ClientEntityMap::default(),
-    ));
-
-    // Inform the client that he is ready
-    commands.server_trigger_targets(
-        ToClients {
-            mode: SendMode::Direct(client_entity),
-            event: SetupPlayerServerCommand,
-        },
-        client_entity,
-    );
+    let (spawned, pending) = q_client.get(client_entity)?;
+    if spawned || pending {
+        warn!("Client {} asked to spawn again, ignoring", client_entity);
+        return Ok(());
+    }
+    commands.entity(client_entity).insert(PendingSpawn(None));
     Ok(())
 }
 
+fn start_player_claims(
+    mut q_pending: Query<(Entity, &UserId, &mut PendingSpawn), Added<PendingSpawn>>,
+    config: Res<GameServerConfig>,
+) {
+    for (client_entity, user_id, mut pending) in &mut q_pending {
+        pending.0 = Some(IoTaskPool::get().spawn(claim_player(
+            config.handoff_ref.clone(),
+            config.characters_ref.clone(),
+            ClaimPlayer {
+                user_id: user_id.0,
+                colony: config.colony,
+                client: client_entity,
+                force: false,
+            },
+        )));
+    }
+}
+
+/// Claims the player, waiting for the previous colony to deposit its state. Players that
+/// don't come from another colony are loaded from their saved character.
+async fn claim_player(
+    handoff_ref: ActorRef<Handoff>,
+    characters_ref: ActorRef<Characters>,
+    mut claim: ClaimPlayer,
+) -> PlayerClaim {
+    let started = Instant::now();
+    loop {
+        claim.force = started.elapsed() > HANDOFF_TIMEOUT;
+        match handoff_ref.ask(claim).await {
+            Ok(ClaimResult::Claimed(Some(snapshot))) => {
+                return PlayerClaim::Arrived(Some(snapshot))
+            }
+            Ok(ClaimResult::Claimed(None)) => {
+                let snapshot = match characters_ref.ask(LoadCharacter(claim.user_id)).await {
+                    Ok(character) => character.map(|character| character.snapshot),
+                    Err(e) => {
+                        warn!("Failed to load player {}: {:?}", claim.user_id, e);
+                        None
+                    }
+                };
+                return PlayerClaim::Arrived(snapshot);
+            }
+            Ok(ClaimResult::Held) => return PlayerClaim::Held,
+            Ok(ClaimResult::Pending) => {
+                async_io::Timer::after(HANDOFF_RETRY).await;
+            }
+            Err(e) => {
+                warn!("Failed to claim player {}: {:?}", claim.user_id, e);
+                return PlayerClaim::Arrived(None);
+            }
+        }
+    }
+}
+
+fn spawn_players(
+    mut q_pending: Query<(Entity, &mut PendingSpawn, &Username, Option<&ArrivalNode>)>,
+    q_spawn_point: Query<(&SpawnPoint, &Transform, Option<&VortexNode>)>,
+    q_players: Query<&Transform, With<Player>>,
+    mut rng: GlobalEntropy<WyRand>,
+    mut commands: Commands,
+) {
+    for (client_entity, mut pending, username, arrival) in &mut q_pending {
+        let Some(task) = pending.0.as_mut() else {
+            continue;
+        };
+        let Some(claim) = block_on(future::poll_once(task)) else {
+            continue;
+        };
+        commands
+            .entity(client_entity)
+            .remove::<(PendingSpawn, ArrivalNode)>();
+        let PlayerClaim::Arrived(snapshot) = claim else {
+            warn!("Player {} is already held by {}", username.0, client_entity);
+            continue;
+        };
+
+        // Characters without a faction yet are recruited by one, saving makes it stick
+        let faction_info = snapshot
+            .as_ref()
+            .and_then(|snapshot| snapshot.faction_info.clone())
+            .unwrap_or_else(|| {
+                let faction = STARTING_FACTIONS[rng.gen_range(0..STARTING_FACTIONS.len())];
+                info!("Recruiting {} into {}", username.0, faction);
+                PlayerFactionInfo {
+                    faction,
+                    rank: Rank::default(),
+                }
+            });
+
+        // Pick spawn position
+        let candidates = spawn_candidates(arrival, Some(&faction_info), &q_spawn_point, &q_players);
+        let spawn_point = match candidates.len() {
+            // Scenes without spawn points
+            0 => CLONING_SPAWN_POSITION,
+            len => candidates[rng.gen_range(0..len)],
+        };
+        let translation = spawn_point + Vec3::Y;
+
+        // Create player
+        let health = snapshot
+            .as_ref()
+            .map(|snapshot| Health::from(snapshot.hit_points))
+            .unwrap_or_default();
+        let experience = snapshot
+            .as_ref()
+            .map(|snapshot| Experience(snapshot.experience))
+            .unwrap_or_default();
+        commands.entity(client_entity).insert((
+            Player,
+            Replicated,
+            Transform::from_translation(translation),
+            SpawnProtection(Timer::new(SPAWN_PROTECTION, TimerMode::Once)),
+            health,
+            Inventory::default(),
+            CreatureName(username.0.clone()),
+            faction_info,
+            experience,
+            AuthorizedClient,
+            ClientEntityMap::default(),
+            // Shots are resolved against the same capsule the client moves with
+            (
+                RigidBody::Kinematic,
+                Collider::capsule(0.3, 0.75),
+                CollisionLayers::new(
+                    [GameLayer::Player],
+                    [GameLayer::Area, GameLayer::Sensor, GameLayer::Structure],
+                ),
+            ),
+        ));
+
+        if let Some(snapshot) = snapshot {
+            for (item_id, quantity) in snapshot.items {
+                commands.spawn((
+                    item_id,
+                    Quantity(quantity),
+                    Replicated,
+                    StoredIn(client_entity),
+                ));
+            }
+        }
+
+        // Inform the client that he is ready
+        commands.server_trigger_targets(
+            ToClients {
+                mode: SendMode::Direct(client_entity),
+                event: SetupPlayerServerCommand { translation },
+            },
+            client_entity,
+        );
+    }
+}
+
+/// Hands the state of a leaving player over to the next colony it connects to.
+fn deposit_players(
+    trigger: Trigger<Disconnected>,
+    mut q_client: Query<(
+        &UserId,
+        Option<&Health>,
+        Option<&Contains>,
+        Option<&PlayerFactionInfo>,
+        Option<&Experience>,
+        Option<&mut PendingSpawn>,
+    )>,
+    q_items: Query<(&ItemId, &Quantity)>,
+    config: Res<GameServerConfig>,
+) {
+    let client_entity = trigger.target();
+    let Ok((user_id, health, contains, faction_info, experience, pending)) =
+        q_client.get_mut(client_entity)
+    else {
+        return;
+    };
+    let mut deposit = DepositPlayer {
+        user_id: user_id.0,
+        colony: config.colony,
+        client: client_entity,
+        snapshot: None,
+    };
+
+    // Left before being spawned, pass on whatever was claimed once the claim finishes
+    if let Some(task) = pending.and_then(|mut pending| pending.0.take()) {
+        let handoff_ref = config.handoff_ref.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                // A held player was never spawned by this claim, release it without state
+                if let PlayerClaim::Arrived(snapshot) = task.await {
+                    deposit.snapshot = snapshot;
+                }
+                if let Err(e) = handoff_ref.tell(deposit).await {
+                    warn!("Failed to deposit player: {:?}", e);
+                }
+            })
+            .detach();
+        return;
+    }
+
+    if let Some(health) = health {
+        let snapshot = player_snapshot(health, contains, faction_info, experience, &q_items);
+        save_player(&config, user_id, snapshot.clone());
+        deposit.snapshot = Some(snapshot);
+    }
+
+    if let Err(e) = config.handoff_ref.tell(deposit).try_send() {
+        warn!("Failed to deposit player {}: {:?}", user_id, e);
+    }
+}
+
+fn save_players(
+    q_player: Query<
+        (
+            &UserId,
+            &Health,
+            Option<&Contains>,
+            Option<&PlayerFactionInfo>,
+            Option<&Experience>,
+        ),
+        With<Player>,
+    >,
+    q_items: Query<(&ItemId, &Quantity)>,
+    config: Res<GameServerConfig>,
+) {
+    for (user_id, health, contains, faction_info, experience) in &q_player {
+        let snapshot = player_snapshot(health, contains, faction_info, experience, &q_items);
+        save_player(&config, user_id, snapshot);
+    }
+}
+
+/// Dead players are saved as they come out of cloning, so a relog doesn't bring back a corpse.
+fn save_player(config: &GameServerConfig, user_id: &UserId, mut snapshot: PlayerSnapshot) {
+    let mut colony = config.colony;
+    if Health::from(snapshot.hit_points).is_dead() {
+        snapshot.hit_points = CLONE_HEALTH_80;
+        colony = Colony::Cloning;
+    }
+    let save = SaveCharacter {
+        user_id: user_id.0,
+        colony,
+        snapshot,
+    };
+    if let Err(e) = config.characters_ref.tell(save).try_send() {
+        warn!("Failed to save player {}: {:?}", user_id, e);
+    }
+}
+
+fn player_snapshot(
+    health: &Health,
+    contains: Option<&Contains>,
+    faction_info: Option<&PlayerFactionInfo>,
+    experience: Option<&Experience>,
+    q_items: &Query<(&ItemId, &Quantity)>,
+) -> PlayerSnapshot {
+    let items = contains
+        .into_iter()
+        .flatten()
+        .filter_map(|item_e| q_items.get(*item_e).ok())
+        .map(|(item_id, quantity)| (item_id.clone(), quantity.0))
+        .collect();
+    PlayerSnapshot {
+        hit_points: health.get_health(),
+        items,
+        faction_info: faction_info.cloned(),
+        experience: experience
+            .map(|experience| experience.0)
+            .unwrap_or_default(),
+    }
+}
+
 fn connect_clients(trigger: Trigger<OnAdd, ConnectedClient>) {
     info!("OnAdd ClientConnected {:?}", trigger.target());
     // We don't do here anything at the moment because the client asks to get spawned
@@ -55,3 +344,66 @@ fn despawn_clients(trigger: Trigger<OnRemove, ConnectedClient>, mut commands: Co
     info!("OnRemove ClientConnected {:?}", trigger.target());
     commands.entity(trigger.target()).try_despawn();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{ecs::system::RunSystemOnce, tasks::TaskPool};
+    use bevy_rand::prelude::EntropyPlugin;
+
+    #[test]
+    fn repeated_spawn_command_keeps_items_and_health() {
+        // given
+        let mut app = setup();
+        let snapshot = PlayerSnapshot {
+            hit_points: 100.0,
+            items: vec![
+                (ItemId::new("energy_cell"), 20),
+                (ItemId::new(HACKING_TOOL), 1),
+            ],
+            faction_info: None,
+            experience: 0,
+        };
+        let task = IoTaskPool::get().spawn(async move { PlayerClaim::Arrived(Some(snapshot)) });
+        let client_e = app
+            .world_mut()
+            .spawn((
+                UserId(1),
+                Username("player".to_string()),
+                PendingSpawn(Some(task)),
+            ))
+            .id();
+        while !app.has_component::<Player>(client_e) {
+            app.update();
+        }
+        app.get_mut::<Health>(client_e).take_damage(30.0);
+
+        // when
+        for _ in 0..2 {
+            app.world_mut().trigger(FromClient {
+                client_entity: client_e,
+                event: PlayerSpawnClientCommand,
+            });
+            app.update();
+        }
+
+        // then
+        let stored = app
+            .world_mut()
+            .run_system_once(|q_stored: Query<&StoredIn>| q_stored.iter().count())
+            .unwrap();
+        assert_eq!(stored, 2);
+        assert!(!app.has_component::<PendingSpawn>(client_e));
+        assert_eq!(app.get::<Health>(client_e).get_health(), 70.0);
+    }
+
+    fn setup() -> App {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let mut app = App::new();
+        app.init_time()
+            .add_plugins(EntropyPlugin::<WyRand>::default())
+            .add_systems(PreUpdate, spawn_players)
+            .add_observer(init_clients);
+        app
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+
+/// Rewards players for their deeds and tells them about their promotions.
+pub struct ProgressionRemotePlugin;
+
+impl Plugin for ProgressionRemotePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ProgressionPlugin)
+            .add_observer(award_capture)
+            .add_observer(award_kill)
+            .add_observer(award_delivery)
+            .add_observer(send_rank_up);
+    }
+}
+
+/// Everyone of the capturing faction still at the node shares the capture.
+fn award_capture(
+    trigger: Trigger<EnergyNodeCaptured>,
+    q_node: Query<&Transform, With<EnergyNode>>,
+    q_player: Query<(Entity, &Transform, &PlayerFactionInfo), (With<Player>, Without<Dead>)>,
+    mut commands: Commands,
+) {
+    let Ok(node_tr) = q_node.get(trigger.target()) else {
+        return;
+    };
+    let faction = trigger.event().faction;
+    for (player_e, player_tr, faction_info) in &q_player {
+        if faction_info.faction == faction
+            && player_tr.translation.distance(node_tr.translation) <= CAPTURE_RADIUS
+        {
+            commands.trigger_targets(
+                AwardExperience {
+                    source: ExperienceSource::Capture,
+                },
+                player_e,
+            );
+        }
+    }
+}
+
+/// Killing a player of another faction is worth experience, friendly fire is not.
+fn award_kill(
+    trigger: Trigger<OnAdd, KilledBy>,
+    q_killed: Query<(&KilledBy, &PlayerFactionInfo)>,
+    q_killer: Query<&PlayerFactionInfo>,
+    mut commands: Commands,
+) {
+    let Ok((KilledBy(killer_e), victim_info)) = q_killed.get(trigger.target()) else {
+        return;
+    };
+    let Ok(killer_info) = q_killer.get(*killer_e) else {
+        return;
+    };
+    if killer_info.faction != victim_info.faction {
+        commands.trigger_targets(
+            AwardExperience {
+                source: ExperienceSource::Kill,
+            },
+            *killer_e,
+        );
+    }
+}
+
+/// Using an energy node of the own faction delivers one carried energy cell to it.
+fn award_delivery(
+    trigger: Trigger<UseCommand>,
+    q_node: Query<&OwnershipRegistry, With<EnergyNode>>,
+    q_player: Query<(&PlayerFactionInfo, &Contains), (With<Player>, Without<Dead>)>,
+    mut q_items: Query<(&ItemId, &mut Quantity)>,
+    mut commands: Commands,
+) {
+    let player_e = trigger.event().user;
+    let (Ok(registry), Ok((faction_info, contains))) =
+        (q_node.get(trigger.target()), q_player.get(player_e))
+    else {
+        return;
+    };
+    if registry.permanent_owner() != Some(faction_info.faction) {
+        return;
+    }
+    let Some(item_e) = contains.into_iter().copied().find(|item_e| {
+        q_items
+            .get(*item_e)
+            .is_ok_and(|(item_id, _)| item_id.0 == ENERGY_CELL)
+    }) else {
+        return;
+    };
+    let Ok((_, mut quantity)) = q_items.get_mut(item_e) else {
+        return;
+    };
+    if quantity.0 > 1 {
+        quantity.0 -= 1;
+    } else {
+        commands.entity(item_e).despawn();
+    }
+    commands.trigger_targets(
+        AwardExperience {
+            source: ExperienceSource::Delivery,
+        },
+        player_e,
+    );
+}
+
+fn send_rank_up(trigger: Trigger<RankUpEvent>, mut commands: Commands) {
+    let player_e = trigger.target();
+    let event = *trigger.event();
+    info!("Player {} promoted to {}", player_e, event.rank);
+    commands.server_trigger_targets(
+        ToClients {
+            mode: SendMode::Direct(player_e),
+            event,
+        },
+        player_e,
+    );
+}
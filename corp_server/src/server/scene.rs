@@ -0,0 +1,206 @@
+use crate::server::*;
+use anyhow::Context;
+use avian3d::prelude::*;
+use bevy::{
+    prelude::*,
+    reflect::{serde::TypedReflectDeserializer, TypeRegistry},
+};
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+use serde::de::DeserializeSeed;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Spawns the structures of the colony scene that the server simulates, like vortex gates.
+///
+/// The client loads the same scene for rendering, the server only reads the nodes that carry
+/// components set in Blender with skein. Structures get trimesh colliders like on the client, so
+/// the server can cast rays against walls and doors.
+pub struct ColonyScenePlugin;
+
+impl Plugin for ColonyScenePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((TransformPlugin, PhysicsPlugins::default(), StructurePlugin))
+            .add_systems(Startup, spawn_colony_scene);
+    }
+}
+
+/// Node of a colony scene with the components assigned to it.
+struct SceneNode {
+    name: String,
+    transform: Transform,
+    components: Vec<(String, Value)>,
+    mesh: Option<TriMesh>,
+}
+
+/// Triangles of all primitives of a node mesh, in the space of the node.
+struct TriMesh {
+    vertices: Vec<Vec3>,
+    indices: Vec<[u32; 3]>,
+}
+
+fn spawn_colony_scene(world: &mut World) {
+    let config = world.resource::<GameServerConfig>();
+    let colony = config.colony;
+    let Some(path) = scene_path(&config.assets_dir, colony) else {
+        info!("No scene for {}", colony);
+        return;
+    };
+    let nodes = match read_scene_nodes(&path) {
+        Ok(nodes) => nodes,
+        Err(e) => {
+            error!("Failed to read scene {}: {:?}", path.display(), e);
+            return;
+        }
+    };
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let count = nodes.len();
+    for node in nodes {
+        let mut entity = world.spawn((Name::new(node.name.clone()), node.transform));
+        for (type_path, data) in &node.components {
+            if let Err(e) = insert_component(&mut entity, &registry, type_path, data) {
+                debug!("Skipping {} of {}: {}", type_path, node.name, e);
+            }
+        }
+        if let Some(mesh) = node.mesh.filter(|_| entity.contains::<CreateTriMesh>()) {
+            entity.insert((
+                Collider::trimesh(mesh.vertices, mesh.indices),
+                CollisionMargin(0.05),
+            ));
+        }
+    }
+    info!("Spawned {} nodes of {} scene", count, colony);
+}
+
+/// Replicates the scene structures with the component, clients link them to their own scene
+/// by [`SceneNodeName`].
+pub fn replicate_scene_structure<C: Component>(
+    trigger: Trigger<OnAdd, C>,
+    q_name: Query<&Name>,
+    mut commands: Commands,
+) -> Result {
+    let name = q_name.get(trigger.target())?;
+    commands
+        .entity(trigger.target())
+        .insert((Replicated, SceneNodeName(name.to_string())));
+    Ok(())
+}
+
+fn scene_path(assets_dir: &Path, colony: Colony) -> Option<PathBuf> {
+    let name = match colony {
+        Colony::Cloning | Colony::Iris | Colony::Liberte => colony.to_string().to_lowercase(),
+        Colony::StarMap | Colony::Playground => return None,
+    };
+    Some(
+        assets_dir
+            .join("scenes")
+            .join(&name)
+            .join(format!("{name}.glb")),
+    )
+}
+
+fn insert_component(
+    entity: &mut EntityWorldMut,
+    registry: &TypeRegistry,
+    type_path: &str,
+    data: &Value,
+) -> anyhow::Result<()> {
+    let registration = registry
+        .get_with_type_path(type_path)
+        .context("type is not registered")?;
+    let reflect_component = registration
+        .data::<ReflectComponent>()
+        .context("type is not a component")?;
+    let component = TypedReflectDeserializer::new(registration, registry).deserialize(data)?;
+    reflect_component.insert(entity, component.as_partial_reflect(), registry);
+    Ok(())
+}
+
+/// Reads the nodes with skein components from a glb file, their transforms resolved to the scene
+/// root.
+fn read_scene_nodes(path: &Path) -> anyhow::Result<Vec<SceneNode>> {
+    let gltf = gltf::Gltf::from_slice(&std::fs::read(path)?)?;
+    let scene = gltf
+        .default_scene()
+        .or_else(|| gltf.scenes().next())
+        .context("glb has no scenes")?;
+
+    let mut nodes = Vec::new();
+    let mut stack = scene
+        .nodes()
+        .map(|node| (node, Transform::IDENTITY))
+        .collect::<Vec<_>>();
+    while let Some((node, parent)) = stack.pop() {
+        let matrix = Mat4::from_cols_array_2d(&node.transform().matrix());
+        let transform = parent.mul_transform(Transform::from_matrix(matrix));
+        let extras = node
+            .extras()
+            .as_ref()
+            .map(|extras| serde_json::from_str::<Value>(extras.get()))
+            .transpose()?;
+        if let Some(components) = extras
+            .as_ref()
+            .and_then(|extras| extras["skein"].as_array())
+        {
+            let name = node.name().unwrap_or_default().to_string();
+            let mesh = node.mesh().and_then(|mesh| {
+                read_trimesh(&mesh, gltf.blob.as_deref())
+                    .inspect_err(|e| warn!("Failed to read mesh of {}: {:?}", name, e))
+                    .ok()
+            });
+            nodes.push(SceneNode {
+                name,
+                transform,
+                components: components
+                    .iter()
+                    .filter_map(Value::as_object)
+                    .flatten()
+                    .map(|(type_path, data)| (type_path.clone(), data.clone()))
+                    .collect(),
+                mesh,
+            });
+        }
+        stack.extend(node.children().map(|child| (child, transform)));
+    }
+    Ok(nodes)
+}
+
+fn read_trimesh(mesh: &gltf::Mesh, blob: Option<&[u8]>) -> anyhow::Result<TriMesh> {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for primitive in mesh.primitives() {
+        if primitive.mode() != gltf::mesh::Mode::Triangles {
+            continue;
+        }
+        // Scenes are exported as glb, everything is in the binary chunk
+        let reader = primitive.reader(|buffer| match buffer.source() {
+            gltf::buffer::Source::Bin => blob,
+            gltf::buffer::Source::Uri(_) => None,
+        });
+        let first = vertices.len() as u32;
+        let positions = reader
+            .read_positions()
+            .context("primitive has no positions")?;
+        vertices.extend(positions.map(Vec3::from));
+        let count = vertices.len() as u32 - first;
+        let primitive_indices = match reader.read_indices() {
+            Some(read) => read.into_u32().collect::<Vec<_>>(),
+            None => (0..count).collect(),
+        };
+        anyhow::ensure!(
+            primitive_indices.iter().all(|index| *index < count),
+            "index out of bounds"
+        );
+        indices.extend(primitive_indices.chunks_exact(3).map(|triangle| {
+            [
+                first + triangle[0],
+                first + triangle[1],
+                first + triangle[2],
+            ]
+        }));
+    }
+    anyhow::ensure!(!indices.is_empty(), "mesh has no triangles");
+    Ok(TriMesh { vertices, indices })
+}
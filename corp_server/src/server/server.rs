@@ -10,11 +10,14 @@ use aeronet_webtransport::{
     server::{SessionRequest, SessionResponse, WebTransportServer, WebTransportServerPlugin},
     wtransport,
 };
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, IoTaskPool, Task},
+};
 use bevy_replicon::prelude::*;
 use corp_shared::prelude::*;
 use corp_types::prelude::*;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use surf::http::convert::json;
 
 pub struct ServerNetPlugin;
@@ -28,12 +31,17 @@ impl Plugin for ServerNetPlugin {
             RepliconPlugins.set(ServerPlugin {
                 // 1 frame lasts `1.0 / TICK_RATE` anyway
                 tick_policy: TickPolicy::EveryFrame,
+                // entities are revealed per client by the `InterestPlugin`
+                visibility_policy: VisibilityPolicy::Whitelist,
                 ..Default::default()
             }),
             AeronetRepliconServerPlugin,
             ReplicateRulesPlugin,
+            InterestPlugin,
+            SessionsPlugin,
         ))
         .add_systems(Startup, open_server)
+        .add_systems(PreUpdate, apply_admissions)
         .add_observer(on_opened)
         .add_observer(on_closed)
         .add_observer(on_session_request)
@@ -80,8 +88,26 @@ fn on_closed(trigger: Trigger<Closed>) {
     panic!("server closed: {:?}", trigger.event());
 }
 
+const ADMISSION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// User of an accepted session request.
+struct Admission {
+    user: User,
+    /// Transfer ticket presented with the request, given back unless the session is accepted.
+    ticket: Option<TicketRedemption>,
+    arrival: Option<Arrival>,
+}
+
+/// Session request waiting for its token to be validated off the game thread.
+#[derive(Component)]
+struct PendingAdmission {
+    token: String,
+    requested_at: Instant,
+    task: Task<Result<Option<Admission>, String>>,
+}
+
 fn on_session_request(
-    mut request: Trigger<SessionRequest>,
+    request: Trigger<SessionRequest>,
     clients: Query<&ChildOf>,
     game_server_config: Res<GameServerConfig>,
     mut commands: Commands,
@@ -92,40 +118,138 @@ fn on_session_request(
     debug!("\"{client}\" connecting to \"{server}\" with headers:");
 
     let mut token = String::new();
+    let mut ticket = None;
     for (header_key, header_value) in &request.headers {
         debug!("  {header_key}: {header_value}");
 
         // Extract username and password from headers
         match header_key.as_str() {
             "x-token" => token = header_value.clone(),
+            "x-ticket" => ticket = Some(header_value.clone()),
             _ => {}
         }
     }
-    match game_server_config
-        .tokens_ref
-        .ask(IsTokenValid(token.clone()))
-        .blocking_send()
-    {
-        Ok(true) => {
-            info!("Accepted token {} from {:?}", token, client);
-            commands.entity(client).insert(AuthToken(token));
-            request.respond(SessionResponse::Accepted);
-        }
-        Ok(false) => {
-            warn!("Rejected token \"{}\" from {:?}", token, client);
-            request.respond(SessionResponse::NotFound)
-        }
-        Err(e) => {
-            warn!(
-                "Error while validating token {} from {:?} reason: {:?}",
-                token, client, e
-            );
-            request.respond(SessionResponse::NotFound)
+
+    // The request stays parked until `apply_admissions` inserts a `SessionResponse`. The ticket is
+    // redeemed in one step, so two sessions can't both use it, and it is restored when the
+    // admission is dropped on timeout or disconnect.
+    let tokens_ref = game_server_config.tokens_ref.clone();
+    let tickets_ref = game_server_config.tickets_ref.clone();
+    let colony = game_server_config.colony;
+    let token_to_validate = token.clone();
+    let task = IoTaskPool::get().spawn(async move {
+        let user = tokens_ref
+            .ask(ResolveToken(token_to_validate))
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let Some(user) = user else {
+            return Ok(None);
+        };
+
+        // Players enter colonies only on the orders of the server, the star map is open to all
+        let mut arrival = None;
+        let mut redemption = None;
+        if !colony.is_star_map() {
+            let Some(ticket) = ticket else {
+                return Err(format!("missing transfer ticket to {colony}"));
+            };
+            let pending_redemption =
+                TicketRedemption::new(tickets_ref.clone(), ticket.clone(), client);
+            arrival = tickets_ref
+                .ask(RedeemTicket {
+                    ticket,
+                    user_id: user.id,
+                    colony,
+                    client,
+                })
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            if arrival.is_none() {
+                return Err(format!("invalid transfer ticket to {colony}"));
+            }
+            redemption = Some(pending_redemption);
         }
-    }
+        Ok(Some(Admission {
+            user,
+            ticket: redemption,
+            arrival,
+        }))
+    });
+    commands.entity(client).insert(PendingAdmission {
+        token,
+        requested_at: Instant::now(),
+        task,
+    });
     Ok(())
 }
 
+fn apply_admissions(
+    mut commands: Commands,
+    mut q_pending: Query<(Entity, &mut PendingAdmission)>,
+    config: Res<GameServerConfig>,
+) {
+    for (client, mut pending) in &mut q_pending {
+        let response = match block_on(future::poll_once(&mut pending.task)) {
+            Some(Ok(Some(Admission {
+                user,
+                ticket,
+                arrival,
+            }))) => {
+                info!(
+                    "Accepted token {} from {:?} as user {}",
+                    pending.token, client, user.username
+                );
+                if let Some(ticket) = ticket {
+                    ticket.keep();
+                }
+                // Kicks the older session of the same user from whichever colony it is on
+                let session = ActiveSession {
+                    user_id: user.id,
+                    username: user.username.clone(),
+                    token: pending.token.clone(),
+                    colony: config.colony,
+                    client,
+                };
+                if let Err(e) = config.sessions_ref.tell(ClaimSession(session)).try_send() {
+                    warn!("Failed to claim session of user {}: {:?}", user.username, e);
+                }
+                commands.entity(client).insert((
+                    AuthToken(pending.token.clone()),
+                    UserId(user.id),
+                    Username(user.username),
+                ));
+                if let Some(node) = arrival.and_then(|arrival| arrival.node) {
+                    commands.entity(client).insert(ArrivalNode(node));
+                }
+                SessionResponse::Accepted
+            }
+            Some(Ok(None)) => {
+                warn!("Rejected token \"{}\" from {:?}", pending.token, client);
+                SessionResponse::NotFound
+            }
+            Some(Err(e)) => {
+                warn!(
+                    "Error while validating token {} from {:?} reason: {}",
+                    pending.token, client, e
+                );
+                SessionResponse::NotFound
+            }
+            None if pending.requested_at.elapsed() > ADMISSION_TIMEOUT => {
+                warn!(
+                    "Timed out validating token {} from {:?}",
+                    pending.token, client
+                );
+                SessionResponse::NotFound
+            }
+            None => continue,
+        };
+        commands
+            .entity(client)
+            .remove::<PendingAdmission>()
+            .insert(response);
+    }
+}
+
 fn on_connected(
     trigger: Trigger<OnAdd, Session>,
     clients: Query<&ChildOf>,
@@ -0,0 +1,78 @@
+use crate::server::*;
+use aeronet::io::connection::{Disconnect, Disconnected};
+use async_channel::Receiver;
+use bevy::prelude::*;
+use corp_shared::prelude::*;
+
+/// Commands from the [`SessionRegistry`] waiting to be applied to this colony.
+#[derive(Resource)]
+struct ColonyInbox(Receiver<ColonyCommand>);
+
+/// Keeps the [`SessionRegistry`] up to date with the sessions connected to this colony.
+pub struct SessionsPlugin;
+
+impl Plugin for SessionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, register_colony)
+            .add_systems(PreUpdate, apply_colony_commands)
+            .add_observer(release_session);
+    }
+}
+
+fn register_colony(mut commands: Commands, config: Res<GameServerConfig>) {
+    let (inbox, receiver) = async_channel::unbounded();
+    if let Err(e) = config
+        .sessions_ref
+        .tell(RegisterColony {
+            colony: config.colony,
+            inbox,
+        })
+        .try_send()
+    {
+        error!(
+            "Failed to register {} in session registry: {:?}",
+            config.colony, e
+        );
+    }
+    commands.insert_resource(ColonyInbox(receiver));
+}
+
+fn apply_colony_commands(inbox: Res<ColonyInbox>, mut commands: Commands) {
+    while let Ok(command) = inbox.0.try_recv() {
+        match command {
+            ColonyCommand::Kick { client, reason } => {
+                info!("Kicking \"{client}\": {reason}");
+                if let Ok(mut entity) = commands.get_entity(client) {
+                    entity.trigger(Disconnect::new(reason));
+                }
+            }
+            ColonyCommand::Transfer { client, colony } => {
+                if let Ok(mut entity) = commands.get_entity(client) {
+                    entity.trigger(TransferPlayer::to(colony));
+                }
+            }
+        }
+    }
+}
+
+fn release_session(
+    trigger: Trigger<Disconnected>,
+    q_user: Query<&UserId>,
+    config: Res<GameServerConfig>,
+) {
+    let client = trigger.target();
+    let Ok(user_id) = q_user.get(client) else {
+        return;
+    };
+    if let Err(e) = config
+        .sessions_ref
+        .tell(ReleaseSession {
+            user_id: user_id.0,
+            colony: config.colony,
+            client,
+        })
+        .try_send()
+    {
+        warn!("Failed to release session of user {}: {:?}", user_id, e);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::server::*;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+use std::time::Duration;
+
+/// Guards freshly spawned players until they act or the protection runs out.
+pub struct SpawnPlugin;
+
+impl Plugin for SpawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, tick_spawn_protection)
+            .add_observer(break_protection_on::<UseRequest>)
+            .add_observer(break_protection_on::<LootCommand>)
+            .add_observer(break_protection_on::<FireRequest>);
+    }
+}
+
+pub const SPAWN_PROTECTION: Duration = Duration::from_secs(5);
+/// Other players closer than this to a spawn point occupy it.
+const SPAWN_CLEARANCE: f32 = 1.5;
+
+/// Picks where a player enters the colony.
+///
+/// The vortex node the player arrived through comes first. Otherwise the free spawn points of
+/// the player's faction are preferred over the ones open to all factions.
+pub fn spawn_candidates(
+    arrival: Option<&ArrivalNode>,
+    faction_info: Option<&PlayerFactionInfo>,
+    q_spawn_point: &Query<(&SpawnPoint, &Transform, Option<&VortexNode>)>,
+    q_players: &Query<&Transform, With<Player>>,
+) -> Vec<Vec3> {
+    if let Some(ArrivalNode(id)) = arrival {
+        let node = q_spawn_point
+            .iter()
+            .find(|(_, _, node)| node.is_some_and(|node| node.id == *id));
+        if let Some((_, transform, _)) = node {
+            return vec![transform.translation];
+        }
+        warn!("Arrival vortex node {} does not exist", id);
+    }
+
+    let faction = faction_info.map(|info| info.faction);
+    let mut candidates = q_spawn_point
+        .iter()
+        .filter(|(point, _, _)| faction.is_some() && point.faction == faction)
+        .map(|(_, transform, _)| transform.translation)
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        candidates = q_spawn_point
+            .iter()
+            .filter(|(point, _, _)| point.faction.is_none())
+            .map(|(_, transform, _)| transform.translation)
+            .collect();
+    }
+
+    let free = candidates
+        .iter()
+        .copied()
+        .filter(|point| {
+            q_players
+                .iter()
+                .all(|player_tr| player_tr.translation.distance(*point) > SPAWN_CLEARANCE)
+        })
+        .collect::<Vec<_>>();
+    // Crowded colonies stack players on occupied points rather than not spawning them
+    if free.is_empty() {
+        candidates
+    } else {
+        free
+    }
+}
+
+fn tick_spawn_protection(
+    mut q_protection: Query<(Entity, &mut SpawnProtection)>,
+    time: Res<Time<Fixed>>,
+    mut commands: Commands,
+) {
+    for (player_e, mut protection) in &mut q_protection {
+        if protection.0.tick(time.delta()).finished() {
+            commands.entity(player_e).remove::<SpawnProtection>();
+        }
+    }
+}
+
+fn break_protection_on<E: Event>(
+    trigger: Trigger<FromClient<E>>,
+    q_protection: Query<(), With<SpawnProtection>>,
+    mut commands: Commands,
+) {
+    let client_entity = trigger.client_entity;
+    if q_protection.contains(client_entity) {
+        debug!("Spawn protection of {} broken", client_entity);
+        commands.entity(client_entity).remove::<SpawnProtection>();
+    }
+}
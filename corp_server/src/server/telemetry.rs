@@ -0,0 +1,150 @@
+use crate::{
+    metrics::{ColonyMetrics, ReportColonyMetrics},
+    server::GameServerConfig,
+};
+use aeronet::io::{packet::PacketStats, Session};
+use bevy::{platform::collections::HashMap, prelude::*, time::common_conditions::on_timer};
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+const REPORT_INTERVAL_SECS: f32 = 1.0;
+const TICK_SAMPLES: usize = 300;
+const TICK_QUANTILES: [f64; 3] = [0.5, 0.95, 0.99];
+
+/// Durations of the most recent ticks.
+#[derive(Resource, Default)]
+struct TickSamples {
+    started: Option<Instant>,
+    samples: VecDeque<Duration>,
+}
+
+impl TickSamples {
+    fn quantiles(&self) -> Vec<(f64, f64)> {
+        let mut sorted = self.samples.iter().copied().collect::<Vec<_>>();
+        sorted.sort();
+        TICK_QUANTILES
+            .iter()
+            .filter_map(|quantile| {
+                let last = sorted.len().checked_sub(1)?;
+                let index = (last as f64 * quantile).round() as usize;
+                Some((*quantile, sorted[index].as_secs_f64()))
+            })
+            .collect()
+    }
+}
+
+/// Triggers received from clients since start, keyed by trigger type.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct TriggerCounts(BTreeMap<&'static str, u64>);
+
+/// Bytes sent to each client at the previous report.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct SentBytes(HashMap<Entity, usize>);
+
+/// Records numbers about a colony server and reports them to the metrics registry.
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TickSamples>()
+            .init_resource::<TriggerCounts>()
+            .init_resource::<SentBytes>()
+            .add_systems(First, start_tick)
+            .add_systems(
+                Last,
+                (
+                    end_tick,
+                    report_metrics.run_if(on_timer(Duration::from_secs_f32(REPORT_INTERVAL_SECS))),
+                )
+                    .chain(),
+            )
+            .add_observer(count_client_trigger::<PlayerSpawnClientCommand>)
+            .add_observer(count_client_trigger::<PlayerMoveClientCommand>)
+            .add_observer(count_client_trigger::<UseRequest>)
+            .add_observer(count_client_trigger::<LootCommand>)
+            .add_observer(count_client_trigger::<SplitStackRequest>)
+            .add_observer(count_client_trigger::<MergeStackRequest>)
+            .add_observer(count_client_trigger::<KillMeCommand>)
+            .add_observer(count_client_trigger::<FireRequest>)
+            .add_observer(count_client_trigger::<ColonyTravelClientCommand>);
+    }
+}
+
+fn start_tick(mut ticks: ResMut<TickSamples>) {
+    ticks.started = Some(Instant::now());
+}
+
+fn end_tick(mut ticks: ResMut<TickSamples>) {
+    let Some(started) = ticks.started.take() else {
+        return;
+    };
+    if ticks.samples.len() == TICK_SAMPLES {
+        ticks.samples.pop_front();
+    }
+    ticks.samples.push_back(started.elapsed());
+}
+
+fn count_client_trigger<E: Event>(
+    _trigger: Trigger<FromClient<E>>,
+    mut counts: ResMut<TriggerCounts>,
+) {
+    let name = std::any::type_name::<E>();
+    let name = name.rsplit("::").next().unwrap_or(name);
+    *counts.entry(name).or_default() += 1;
+}
+
+fn report_metrics(
+    config: Res<GameServerConfig>,
+    ticks: Res<TickSamples>,
+    counts: Res<TriggerCounts>,
+    mut sent_bytes: ResMut<SentBytes>,
+    q_sessions: Query<(Entity, &PacketStats, Option<&UserId>), With<Session>>,
+    q_players: Query<(), With<Player>>,
+    q_backpacks: Query<(), With<Backpack>>,
+    q_items: Query<(), With<Item>>,
+) {
+    // Rebuilt from the connected sessions, disconnected clients drop out of the report
+    let mut replication_bytes_per_sec = BTreeMap::new();
+    let mut replication_bytes_per_sec_total = 0.0;
+    let mut current_bytes = HashMap::default();
+    for (client_e, stats, user_id) in &q_sessions {
+        let bytes = stats.bytes_sent;
+        let previous = sent_bytes.get(&client_e).copied().unwrap_or(bytes);
+        let per_sec = bytes.saturating_sub(previous) as f64 / f64::from(REPORT_INTERVAL_SECS);
+        if let Some(user_id) = user_id {
+            replication_bytes_per_sec.insert(user_id.0, per_sec);
+        }
+        replication_bytes_per_sec_total += per_sec;
+        current_bytes.insert(client_e, bytes);
+    }
+    **sent_bytes = current_bytes;
+
+    let entities = BTreeMap::from([
+        ("players", q_players.iter().count()),
+        ("backpacks", q_backpacks.iter().count()),
+        ("items", q_items.iter().count()),
+    ]);
+
+    let metrics = ColonyMetrics {
+        colony: config.colony,
+        tick_budget_secs: 1.0 / f64::from(TICK_RATE),
+        tick_duration_secs: ticks.quantiles(),
+        entities,
+        connected_sessions: q_sessions.iter().count(),
+        replication_bytes_per_sec,
+        replication_bytes_per_sec_total,
+        client_triggers_total: counts.0.clone(),
+    };
+
+    if let Err(e) = config
+        .metrics_ref
+        .tell(ReportColonyMetrics(metrics))
+        .try_send()
+    {
+        warn!("Failed to report {} metrics: {:?}", config.colony, e);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::server::*;
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, IoTaskPool, Task},
+    time::common_conditions::on_timer,
+};
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+use kameo::actor::ActorRef;
+use std::{collections::HashMap, time::Duration};
+
+const CONTROL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Lets factions capture the energy nodes of the colony, which decides who controls it.
+pub struct TerritoryPlugin;
+
+impl Plugin for TerritoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColonyControl>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    capture_energy_nodes_system,
+                    update_colony_control_system,
+                    apply_colony_control_system,
+                    report_colony_control.run_if(resource_changed::<ColonyControl>),
+                )
+                    .chain(),
+            )
+            .add_observer(replicate_scene_structure::<EnergyNode>)
+            .add_observer(on_capture_started);
+    }
+}
+
+/// Shows the controllers reported by the colonies on the star map.
+pub struct StarMapControlPlugin;
+
+impl Plugin for StarMapControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingControlList>().add_systems(
+            FixedUpdate,
+            (
+                poll_colony_control.run_if(on_timer(CONTROL_POLL_INTERVAL)),
+                apply_colony_control_list,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+struct PendingControlList(Option<Task<HashMap<Colony, Option<Faction>>>>);
+
+fn on_capture_started(trigger: Trigger<EnergyNodeCaptureStarted>) {
+    info!(
+        "{} started capturing energy node {}",
+        trigger.event().faction,
+        trigger.target()
+    );
+}
+
+fn report_colony_control(control: Res<ColonyControl>, config: Res<GameServerConfig>) {
+    let report = ReportColonyControl {
+        colony: config.colony,
+        controller: control.controller,
+    };
+    if let Err(e) = config.control_ref.tell(report).try_send() {
+        warn!("Failed to report {} control: {:?}", config.colony, e);
+    }
+}
+
+fn poll_colony_control(mut pending: ResMut<PendingControlList>, config: Res<GameServerConfig>) {
+    if pending.0.is_some() {
+        return;
+    }
+    let control_ref = config.control_ref.clone();
+    pending.0 = Some(IoTaskPool::get().spawn(list_colony_control(control_ref)));
+}
+
+async fn list_colony_control(
+    control_ref: ActorRef<ControlBoard>,
+) -> HashMap<Colony, Option<Faction>> {
+    control_ref
+        .ask(ListColonyControl)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to list colony control: {:?}", e);
+            HashMap::new()
+        })
+}
+
+fn apply_colony_control_list(
+    mut pending: ResMut<PendingControlList>,
+    mut q_controller: Query<&mut ColonyController>,
+    mut commands: Commands,
+) {
+    let Some(task) = pending.0.as_mut() else {
+        return;
+    };
+    let Some(mut controllers) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    pending.0 = None;
+
+    for mut colony_controller in &mut q_controller {
+        if let Some(controller) = controllers.remove(&colony_controller.colony) {
+            if colony_controller.controller != controller {
+                colony_controller.controller = controller;
+            }
+        }
+    }
+    for (colony, controller) in controllers {
+        commands.spawn((ColonyController { colony, controller }, Replicated));
+    }
+}
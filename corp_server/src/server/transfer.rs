@@ -0,0 +1,142 @@
+use crate::server::*;
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, IoTaskPool, Task},
+};
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+
+/// Sends the player of the client entity it's triggered on to another colony.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct TransferPlayer {
+    pub colony: Colony,
+    /// Vortex node the player arrives at, any node of the colony when not set.
+    pub node: Option<u32>,
+}
+
+impl TransferPlayer {
+    pub fn to(colony: Colony) -> Self {
+        Self { colony, node: None }
+    }
+}
+
+/// Vortex node the player of the client entity arrives at in this colony.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ArrivalNode(pub u32);
+
+/// Colony a player logging in on the star map was saved in, it's sent back there once known.
+#[derive(Component)]
+struct PendingReturn(Task<Option<Colony>>);
+
+/// Decides which colony a player travels to and issues the tickets for it.
+pub struct TransferPlugin;
+
+impl Plugin for TransferPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, return_players)
+            .add_observer(transfer_player)
+            .add_observer(on_colony_travel_command)
+            .add_observer(load_last_colony);
+    }
+}
+
+/// Players log in on the star map, those who left the game in a colony continue there.
+fn load_last_colony(
+    trigger: Trigger<OnAdd, AuthorizedClient>,
+    q_user: Query<&UserId>,
+    config: Res<GameServerConfig>,
+    mut commands: Commands,
+) -> Result {
+    if !config.colony.is_star_map() {
+        return Ok(());
+    }
+    let client_entity = trigger.target();
+    let user_id = q_user.get(client_entity)?.0;
+    let characters_ref = config.characters_ref.clone();
+    let task = IoTaskPool::get().spawn(async move {
+        match characters_ref.ask(LoadCharacter(user_id)).await {
+            Ok(character) => character.and_then(|character| character.last_colony),
+            Err(e) => {
+                warn!("Failed to load last colony of user {}: {:?}", user_id, e);
+                None
+            }
+        }
+    });
+    commands.entity(client_entity).insert(PendingReturn(task));
+    Ok(())
+}
+
+fn return_players(mut q_pending: Query<(Entity, &mut PendingReturn)>, mut commands: Commands) {
+    for (client_entity, mut pending) in &mut q_pending {
+        let Some(last_colony) = block_on(future::poll_once(&mut pending.0)) else {
+            continue;
+        };
+        commands.entity(client_entity).remove::<PendingReturn>();
+        if let Some(colony) = last_colony.filter(|colony| !colony.is_star_map()) {
+            commands
+                .entity(client_entity)
+                .trigger(TransferPlayer::to(colony));
+        }
+    }
+}
+
+fn transfer_player(
+    trigger: Trigger<TransferPlayer>,
+    q_user: Query<&UserId>,
+    config: Res<GameServerConfig>,
+    mut commands: Commands,
+) -> Result {
+    let client_entity = trigger.target();
+    let TransferPlayer { colony, node } = *trigger.event();
+    let user_id = q_user.get(client_entity)?;
+
+    // Granted before the client hears about it, so it's always there when the client connects
+    let ticket = TransferTickets::new_ticket();
+    if let Err(e) = config
+        .tickets_ref
+        .tell(GrantTicket {
+            ticket: ticket.clone(),
+            user_id: user_id.0,
+            colony,
+            node,
+        })
+        .try_send()
+    {
+        warn!("Failed to grant ticket to user {}: {:?}", user_id, e);
+        return Ok(());
+    }
+
+    info!(
+        "Transferring user {} from {} to {}",
+        user_id, config.colony, colony
+    );
+    commands.server_trigger(ToClients {
+        mode: SendMode::Direct(client_entity),
+        event: TransferToColony { colony, ticket },
+    });
+    Ok(())
+}
+
+fn on_colony_travel_command(
+    trigger: Trigger<FromClient<ColonyTravelClientCommand>>,
+    config: Res<GameServerConfig>,
+    mut commands: Commands,
+) {
+    let client_entity = trigger.client_entity;
+    let colony = trigger.event().event.colony;
+    if !can_travel(config.colony, colony) {
+        warn!(
+            "Client {} is not allowed to travel from {} to {}",
+            client_entity, config.colony, colony
+        );
+        return;
+    }
+    commands
+        .entity(client_entity)
+        .trigger(TransferPlayer::to(colony));
+}
+
+/// Only the star map lets players pick where they go, colonies are left through vortex gates.
+fn can_travel(from: Colony, to: Colony) -> bool {
+    from == Colony::StarMap && !matches!(to, Colony::StarMap | Colony::Cloning)
+}
@@ -0,0 +1,96 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use corp_shared::prelude::*;
+
+/// Checks that players can reach what they ask to use, then triggers a [`UseCommand`] on it for
+/// the structure to handle. Structures with a [`UseDuration`] are channeled first.
+pub struct UsablePlugin;
+
+impl Plugin for UsablePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_use_request)
+            .add_observer(on_use_channel_completed);
+    }
+}
+
+fn on_use_request(
+    trigger: Trigger<FromClient<UseRequest>>,
+    q_player: Query<(&Transform, Has<Channeling>), (With<Player>, Without<Dead>)>,
+    q_usable: Query<(&Transform, Option<&Collider>, Option<&UseDuration>), With<Use>>,
+    q_spatial: SpatialQuery,
+    mut commands: Commands,
+) {
+    let client_e = trigger.client_entity;
+    let target = trigger.event().event.target;
+    let Ok((player_tr, channeling)) = q_player.get(client_e) else {
+        return;
+    };
+    let Ok((target_tr, collider, use_duration)) = q_usable.get(target) else {
+        warn!(
+            "Client {} tried to use {} which is not usable",
+            client_e, target
+        );
+        return;
+    };
+
+    // Closest point of the target, the client reaches the target's surface not its center
+    let origin = use_origin(player_tr.translation);
+    let point = collider.map_or(target_tr.translation, |collider| {
+        collider
+            .project_point(target_tr.translation, target_tr.rotation, origin, true)
+            .0
+    });
+    let distance = origin.distance(point);
+    if distance > LOOKUP_RANGE + USE_RANGE_TOLERANCE {
+        warn!(
+            "Client {} is {:.1} away from {}, out of reach",
+            client_e, distance, target
+        );
+        return;
+    }
+
+    if let Ok(direction) = Dir3::new(point - origin) {
+        let hit = q_spatial.cast_ray(
+            origin,
+            direction,
+            distance + USE_RANGE_TOLERANCE,
+            true,
+            &SpatialQueryFilter::from_mask(USABLE_LAYERS),
+        );
+        if let Some(hit) = hit.filter(|hit| hit.entity != target) {
+            warn!(
+                "Client {} can't see {}, {} is in the way",
+                client_e, target, hit.entity
+            );
+            return;
+        }
+    }
+
+    match use_duration {
+        Some(_) if channeling => {
+            debug!("Client {} is busy, not using {}", client_e, target);
+        }
+        Some(use_duration) => {
+            commands.entity(client_e).insert(Channeling::new(
+                ChannelAction::Use,
+                target,
+                use_duration.0,
+            ));
+        }
+        None => {
+            commands.trigger_targets(UseCommand::new(client_e), target);
+        }
+    }
+}
+
+fn on_use_channel_completed(
+    trigger: Trigger<ChannelCompleted>,
+    q_usable: Query<(), With<Use>>,
+    mut commands: Commands,
+) {
+    let ChannelCompleted { action, target } = *trigger.event();
+    if action == ChannelAction::Use && q_usable.contains(target) {
+        commands.trigger_targets(UseCommand::new(trigger.target()), target);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::server::*;
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use corp_shared::prelude::*;
+use std::time::Duration;
+
+/// Sends players that walk into a vortex gate to the destination of the gate.
+pub struct VortexPlugin;
+
+impl Plugin for VortexPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (tick_vortex_cooldowns, enter_vortex_gates).chain(),
+        )
+        .add_observer(on_player_arrived);
+    }
+}
+
+/// Arriving players may be standing in a gate, they have to step out before it takes them.
+const ARRIVAL_COOLDOWN: Duration = Duration::from_secs(3);
+/// Keeps the gate from sending the player again while the client switches colonies.
+const TRANSFER_COOLDOWN: Duration = Duration::from_secs(10);
+const DENIED_COOLDOWN: Duration = Duration::from_secs(2);
+
+/// Vortex gates ignore the player until the timer finishes.
+#[derive(Component, Debug)]
+struct VortexCooldown(Timer);
+
+impl VortexCooldown {
+    fn new(duration: Duration) -> Self {
+        Self(Timer::new(duration, TimerMode::Once))
+    }
+}
+
+fn on_player_arrived(trigger: Trigger<OnAdd, Player>, mut commands: Commands) {
+    commands
+        .entity(trigger.target())
+        .insert(VortexCooldown::new(ARRIVAL_COOLDOWN));
+}
+
+fn tick_vortex_cooldowns(
+    mut q_cooldown: Query<(Entity, &mut VortexCooldown)>,
+    time: Res<Time<Fixed>>,
+    mut commands: Commands,
+) {
+    for (entity, mut cooldown) in &mut q_cooldown {
+        if cooldown.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<VortexCooldown>();
+        }
+    }
+}
+
+fn enter_vortex_gates(
+    q_player: Query<
+        (Entity, &Transform, Option<&PlayerFactionInfo>),
+        (With<Player>, Without<VortexCooldown>, Without<Dead>),
+    >,
+    q_gate: Query<(&VortexGate, &Transform, &Collider)>,
+    config: Res<GameServerConfig>,
+    mut commands: Commands,
+) {
+    for (player_e, player_tr, faction_info) in &q_player {
+        let Some(gate) = q_gate.iter().find_map(|(gate, gate_tr, collider)| {
+            collider
+                .contains_point(gate_tr.translation, gate_tr.rotation, player_tr.translation)
+                .then_some(gate)
+        }) else {
+            continue;
+        };
+
+        if gate.colony == config.colony || !gate.allows(faction_info) {
+            info!("Vortex gate to {} denied {}", gate.colony, player_e);
+            commands
+                .entity(player_e)
+                .insert(VortexCooldown::new(DENIED_COOLDOWN));
+            continue;
+        }
+
+        info!("Vort {} to {}", player_e, gate.colony);
+        commands
+            .entity(player_e)
+            .insert(VortexCooldown::new(TRANSFER_COOLDOWN))
+            .trigger(TransferPlayer {
+                colony: gate.colony,
+                node: gate.node,
+            });
+    }
+}
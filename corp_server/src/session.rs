@@ -0,0 +1,271 @@
+use async_channel::Sender;
+use bevy::prelude::Entity;
+use corp_shared::prelude::Colony;
+use kameo::{
+    actor::{ActorRef, WeakActorRef},
+    error::Infallible,
+    prelude::{ActorStopReason, Context, Message},
+    Actor,
+};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+pub const DUPLICATE_SESSION_REASON: &str = "Logged in from another location";
+
+/// Session of an authenticated user connected to one of the colony servers.
+#[derive(Debug, Clone)]
+pub struct ActiveSession {
+    pub user_id: i64,
+    pub username: String,
+    pub token: String,
+    pub colony: Colony,
+    /// Session entity in the world of the colony server.
+    pub client: Entity,
+}
+
+impl ActiveSession {
+    fn is_same(&self, colony: Colony, client: Entity) -> bool {
+        self.colony == colony && self.client == client
+    }
+}
+
+/// Commands the registry sends to a colony server.
+#[derive(Debug, Clone)]
+pub enum ColonyCommand {
+    Kick { client: Entity, reason: String },
+    Transfer { client: Entity, colony: Colony },
+}
+
+#[derive(Debug)]
+pub struct RegisterColony {
+    pub colony: Colony,
+    pub inbox: Sender<ColonyCommand>,
+}
+
+/// Makes the session the only active one of its user, kicking the older session if any.
+#[derive(Debug)]
+pub struct ClaimSession(pub ActiveSession);
+
+#[derive(Debug)]
+pub struct ReleaseSession {
+    pub user_id: i64,
+    pub colony: Colony,
+    pub client: Entity,
+}
+
+/// Kicks every session that was admitted with the token.
+#[derive(Debug)]
+pub struct RevokeToken {
+    pub token: String,
+    pub reason: String,
+}
+
+/// Sends the user to another colony, replies if the user was online.
+#[derive(Debug)]
+pub struct MoveUser {
+    pub user_id: i64,
+    pub colony: Colony,
+}
+
+#[derive(Debug)]
+pub struct WhereIs(pub i64);
+
+#[derive(Debug)]
+pub struct ListSessions;
+
+/// Tracks the single active session of every user across all colony servers.
+#[derive(Default, Debug)]
+pub struct SessionRegistry {
+    sessions: HashMap<i64, ActiveSession>,
+    colonies: HashMap<Colony, Sender<ColonyCommand>>,
+}
+
+impl SessionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn kick(&self, session: &ActiveSession, reason: &str) {
+        let command = ColonyCommand::Kick {
+            client: session.client,
+            reason: reason.to_string(),
+        };
+        self.send(session, command);
+    }
+
+    fn send(&self, session: &ActiveSession, command: ColonyCommand) -> bool {
+        let Some(inbox) = self.colonies.get(&session.colony) else {
+            warn!("No inbox registered for colony {}", session.colony);
+            return false;
+        };
+        if let Err(e) = inbox.try_send(command) {
+            warn!(
+                "Failed to send {:?} for user {} to {}",
+                e.into_inner(),
+                session.username,
+                session.colony
+            );
+            return false;
+        }
+        true
+    }
+}
+
+impl Actor for SessionRegistry {
+    type Args = Self;
+    type Error = Infallible;
+
+    async fn on_start(args: Self::Args, _actor_ref: ActorRef<Self>) -> Result<Self, Self::Error> {
+        info!("SessionRegistry started");
+        Ok(args)
+    }
+
+    async fn on_stop(
+        &mut self,
+        _actor_ref: WeakActorRef<Self>,
+        reason: ActorStopReason,
+    ) -> Result<(), Self::Error> {
+        info!("SessionRegistry stopping: {:?}", reason);
+        Ok(())
+    }
+}
+
+impl Message<RegisterColony> for SessionRegistry {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: RegisterColony,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            self.colonies.insert(msg.colony, msg.inbox);
+        }
+    }
+}
+
+impl Message<ClaimSession> for SessionRegistry {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: ClaimSession,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            let session = msg.0;
+            info!(
+                "User {} has an active session on {}",
+                session.username, session.colony
+            );
+            if let Some(older) = self.sessions.insert(session.user_id, session.clone()) {
+                if !older.is_same(session.colony, session.client) {
+                    info!(
+                        "Kicking older session of user {} from {}",
+                        older.username, older.colony
+                    );
+                    self.kick(&older, DUPLICATE_SESSION_REASON);
+                }
+            }
+        }
+    }
+}
+
+impl Message<ReleaseSession> for SessionRegistry {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: ReleaseSession,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            // A kicked session releases after the newer one has claimed, keep the newer one
+            if self
+                .sessions
+                .get(&msg.user_id)
+                .is_some_and(|session| session.is_same(msg.colony, msg.client))
+            {
+                self.sessions.remove(&msg.user_id);
+            }
+        }
+    }
+}
+
+impl Message<RevokeToken> for SessionRegistry {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: RevokeToken,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            let revoked = self
+                .sessions
+                .values()
+                .filter(|session| session.token == msg.token)
+                .cloned()
+                .collect::<Vec<_>>();
+            for session in revoked {
+                info!(
+                    "Revoking session of user {} on {}: {}",
+                    session.username, session.colony, msg.reason
+                );
+                self.kick(&session, &msg.reason);
+                self.sessions.remove(&session.user_id);
+            }
+        }
+    }
+}
+
+impl Message<MoveUser> for SessionRegistry {
+    type Reply = bool;
+
+    fn handle(
+        &mut self,
+        msg: MoveUser,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            let Some(session) = self.sessions.get(&msg.user_id) else {
+                return false;
+            };
+            info!(
+                "Moving user {} from {} to {}",
+                session.username, session.colony, msg.colony
+            );
+            self.send(
+                session,
+                ColonyCommand::Transfer {
+                    client: session.client,
+                    colony: msg.colony,
+                },
+            )
+        }
+    }
+}
+
+impl Message<WhereIs> for SessionRegistry {
+    type Reply = Option<ActiveSession>;
+
+    fn handle(
+        &mut self,
+        msg: WhereIs,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.sessions.get(&msg.0).cloned() }
+    }
+}
+
+impl Message<ListSessions> for SessionRegistry {
+    type Reply = Vec<ActiveSession>;
+
+    fn handle(
+        &mut self,
+        _msg: ListSessions,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.sessions.values().cloned().collect() }
+    }
+}
@@ -1,14 +1,113 @@
+use crate::handoff::PlayerSnapshot;
 use chrono::{DateTime, Utc};
+use corp_shared::prelude::{Colony, Faction, ItemId, PlayerFactionInfo, Rank};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
+/// Row of the `characters` table, one per user.
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
-pub struct Player {
-    player_id: i64,
-    username: String,
-    email: String,
-    created_at: DateTime<Utc>,
-    level: i32,
-    experience: i64,
-    coin: i64,
-    last_login: Option<DateTime<Utc>>,
+pub struct CharacterRow {
+    pub user_id: i64,
+    pub hit_points: f64,
+    /// Catalog ids and quantities of the carried stacks as a JSON array of pairs.
+    pub inventory: String,
+    pub faction: Option<String>,
+    pub rank: Option<String>,
+    pub last_colony: Option<String>,
+    pub experience: i64,
+    pub created_at: Option<DateTime<Utc>>,
+    pub last_login: Option<DateTime<Utc>>,
+}
+
+impl CharacterRow {
+    /// Value of the `inventory` column for the carried stacks.
+    pub fn inventory(items: &[(ItemId, u32)]) -> serde_json::Result<String> {
+        serde_json::to_string(items)
+    }
+
+    pub fn snapshot(&self) -> anyhow::Result<PlayerSnapshot> {
+        // Rows saved before stacks were persisted hold one item per id
+        let items = serde_json::from_str::<Vec<(ItemId, u32)>>(&self.inventory).or_else(|e| {
+            serde_json::from_str::<Vec<ItemId>>(&self.inventory)
+                .map(|ids| ids.into_iter().map(|id| (id, 1)).collect())
+                .map_err(|_| e)
+        })?;
+        let faction_info = match (&self.faction, &self.rank) {
+            (Some(faction), Some(rank)) => Some(PlayerFactionInfo {
+                faction: Faction::from_str(faction)?,
+                rank: Rank::from_str(rank)?,
+            }),
+            _ => None,
+        };
+        Ok(PlayerSnapshot {
+            hit_points: self.hit_points as f32,
+            items,
+            faction_info,
+            experience: u64::try_from(self.experience).unwrap_or_default(),
+        })
+    }
+
+    pub fn last_colony(&self) -> Option<Colony> {
+        self.last_colony
+            .as_deref()
+            .and_then(|colony| Colony::from_str(colony).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacks_keep_their_quantity() {
+        // given
+        let items = vec![
+            (ItemId::new("energy_cell"), 20),
+            (ItemId::new("hacking_tool"), 1),
+        ];
+        let row = CharacterRow {
+            inventory: CharacterRow::inventory(&items).unwrap(),
+            ..row()
+        };
+
+        // when
+        let snapshot = row.snapshot().unwrap();
+
+        // then
+        assert_eq!(snapshot.items, items);
+        assert_eq!(snapshot.hit_points, 80.0);
+        assert_eq!(snapshot.experience, 250);
+    }
+
+    #[test]
+    fn items_without_quantity_are_single() {
+        // given
+        let row = CharacterRow {
+            inventory: r#"["hacking_tool"]"#.to_string(),
+            faction: None,
+            rank: None,
+            ..row()
+        };
+
+        // when
+        let snapshot = row.snapshot().unwrap();
+
+        // then
+        assert_eq!(snapshot.items, vec![(ItemId::new("hacking_tool"), 1)]);
+        assert!(snapshot.faction_info.is_none());
+    }
+
+    fn row() -> CharacterRow {
+        CharacterRow {
+            user_id: 1,
+            hit_points: 80.0,
+            inventory: "[]".to_string(),
+            faction: Some(Faction::EC.to_string()),
+            rank: Some(Rank::R2.to_string()),
+            last_colony: None,
+            experience: 250,
+            created_at: None,
+            last_login: None,
+        }
+    }
 }
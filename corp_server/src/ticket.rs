@@ -0,0 +1,289 @@
+use bevy::prelude::Entity;
+use corp_shared::prelude::Colony;
+use kameo::{
+    actor::{ActorRef, WeakActorRef},
+    error::Infallible,
+    prelude::{ActorStopReason, Context, Message},
+    Actor,
+};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+const TICKET_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+struct Ticket {
+    user_id: i64,
+    colony: Colony,
+    node: Option<u32>,
+    expires_at: Instant,
+}
+
+/// Allows the user to connect to the colony once.
+#[derive(Debug)]
+pub struct GrantTicket {
+    pub ticket: String,
+    pub user_id: i64,
+    pub colony: Colony,
+    /// Vortex node the player arrives at.
+    pub node: Option<u32>,
+}
+
+/// Where the player of a redeemed ticket enters the colony.
+#[derive(Debug, Clone, Copy)]
+pub struct Arrival {
+    pub node: Option<u32>,
+}
+
+/// Uses up the ticket if it was granted to the user for the colony, replies with the arrival.
+#[derive(Debug)]
+pub struct RedeemTicket {
+    pub ticket: String,
+    pub user_id: i64,
+    pub colony: Colony,
+    /// Session presenting the ticket, only it may restore the ticket.
+    pub client: Entity,
+}
+
+/// Gives back a ticket the session redeemed but wasn't admitted with.
+#[derive(Debug)]
+pub struct RestoreTicket {
+    pub ticket: String,
+    pub client: Entity,
+}
+
+/// Ticket redeemed by a session that may still be turned away. Dropping it before
+/// [`TicketRedemption::keep`] restores the ticket, so the player can connect again.
+pub struct TicketRedemption {
+    tickets_ref: ActorRef<TransferTickets>,
+    ticket: String,
+    client: Entity,
+    kept: bool,
+}
+
+impl TicketRedemption {
+    /// Starts a redemption before the ticket is redeemed, so that it is restored even if the
+    /// redeeming task is dropped while waiting for the reply.
+    pub fn new(tickets_ref: ActorRef<TransferTickets>, ticket: String, client: Entity) -> Self {
+        Self {
+            tickets_ref,
+            ticket,
+            client,
+            kept: false,
+        }
+    }
+
+    pub fn keep(mut self) {
+        self.kept = true;
+    }
+}
+
+impl Drop for TicketRedemption {
+    fn drop(&mut self) {
+        if self.kept {
+            return;
+        }
+        let restore = RestoreTicket {
+            ticket: std::mem::take(&mut self.ticket),
+            client: self.client,
+        };
+        if let Err(e) = self.tickets_ref.tell(restore).try_send() {
+            warn!("Failed to restore ticket of {}: {:?}", self.client, e);
+        }
+    }
+}
+
+/// Single use tickets for moving a player from one colony to another.
+#[derive(Default, Debug)]
+pub struct TransferTickets {
+    tickets: HashMap<String, Ticket>,
+    /// Tickets used up by a session that hasn't been admitted yet, kept until they expire.
+    redeemed: HashMap<String, (Ticket, Entity)>,
+}
+
+impl TransferTickets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_ticket() -> String {
+        format!("{:032x}", rand::random::<u128>())
+    }
+
+    fn prune(&mut self) {
+        let now = Instant::now();
+        self.tickets.retain(|_, ticket| ticket.expires_at > now);
+        self.redeemed
+            .retain(|_, (ticket, _)| ticket.expires_at > now);
+    }
+
+    fn redeem(&mut self, msg: RedeemTicket) -> Option<Arrival> {
+        self.prune();
+        match self.tickets.remove(&msg.ticket) {
+            Some(ticket) if ticket.user_id == msg.user_id && ticket.colony == msg.colony => {
+                let arrival = Arrival { node: ticket.node };
+                self.redeemed.insert(msg.ticket, (ticket, msg.client));
+                Some(arrival)
+            }
+            Some(ticket) => {
+                warn!(
+                    "Ticket of user {} to {} presented by user {} to {}",
+                    ticket.user_id, ticket.colony, msg.user_id, msg.colony
+                );
+                self.tickets.insert(msg.ticket, ticket);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn restore(&mut self, msg: RestoreTicket) {
+        self.prune();
+        match self.redeemed.remove(&msg.ticket) {
+            Some((ticket, client)) if client == msg.client => {
+                info!(
+                    "Restored ticket of user {} to {}",
+                    ticket.user_id, ticket.colony
+                );
+                self.tickets.insert(msg.ticket, ticket);
+            }
+            Some(redeemed) => {
+                self.redeemed.insert(msg.ticket, redeemed);
+            }
+            None => {}
+        }
+    }
+}
+
+impl Actor for TransferTickets {
+    type Args = Self;
+    type Error = Infallible;
+
+    async fn on_start(args: Self::Args, _actor_ref: ActorRef<Self>) -> Result<Self, Self::Error> {
+        info!("TransferTickets started");
+        Ok(args)
+    }
+
+    async fn on_stop(
+        &mut self,
+        _actor_ref: WeakActorRef<Self>,
+        reason: ActorStopReason,
+    ) -> Result<(), Self::Error> {
+        info!("TransferTickets stopping: {:?}", reason);
+        Ok(())
+    }
+}
+
+impl Message<GrantTicket> for TransferTickets {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: GrantTicket,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            self.tickets.insert(
+                msg.ticket,
+                Ticket {
+                    user_id: msg.user_id,
+                    colony: msg.colony,
+                    node: msg.node,
+                    expires_at: Instant::now() + TICKET_TTL,
+                },
+            );
+        }
+    }
+}
+
+impl Message<RedeemTicket> for TransferTickets {
+    type Reply = Option<Arrival>;
+
+    fn handle(
+        &mut self,
+        msg: RedeemTicket,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.redeem(msg) }
+    }
+}
+
+impl Message<RestoreTicket> for TransferTickets {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        msg: RestoreTicket,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.restore(msg) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticket_is_redeemed_once() {
+        // given
+        let mut tickets = granted_tickets();
+
+        // when
+        let first = tickets.redeem(redeem(1));
+        let second = tickets.redeem(redeem(2));
+
+        // then
+        assert_eq!(first.and_then(|arrival| arrival.node), Some(3));
+        assert!(second.is_none());
+    }
+
+    #[test]
+    fn only_redeeming_session_restores_ticket() {
+        // given
+        let mut tickets = granted_tickets();
+        tickets.redeem(redeem(1));
+
+        // when
+        tickets.restore(RestoreTicket {
+            ticket: "ticket".to_string(),
+            client: Entity::from_raw(2),
+        });
+        let stolen = tickets.redeem(redeem(2));
+        tickets.restore(RestoreTicket {
+            ticket: "ticket".to_string(),
+            client: Entity::from_raw(1),
+        });
+        let restored = tickets.redeem(redeem(2));
+
+        // then
+        assert!(stolen.is_none());
+        assert!(restored.is_some());
+    }
+
+    fn granted_tickets() -> TransferTickets {
+        let mut tickets = TransferTickets::new();
+        tickets.tickets.insert(
+            "ticket".to_string(),
+            Ticket {
+                user_id: 1,
+                colony: Colony::Iris,
+                node: Some(3),
+                expires_at: Instant::now() + TICKET_TTL,
+            },
+        );
+        tickets
+    }
+
+    fn redeem(client: u32) -> RedeemTicket {
+        RedeemTicket {
+            ticket: "ticket".to_string(),
+            user_id: 1,
+            colony: Colony::Iris,
+            client: Entity::from_raw(client),
+        }
+    }
+}
@@ -1,10 +1,28 @@
+use crate::session::{RevokeToken, SessionRegistry};
+use chrono::{DateTime, Utc};
+use corp_login::auth;
 use corp_types::prelude::*;
 use kameo::{
     actor::ActorRef,
     prelude::{Context, Message},
     Actor,
 };
-use std::collections::HashSet;
+use kameo_actors::pubsub::{PubSub, Publish};
+use sqlx::SqlitePool;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tracing::{error, info, warn};
+
+const TOKEN_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+const TOKEN_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+/// How long an unknown token is rejected without asking the database again.
+const TOKEN_MISS_TTL: Duration = Duration::from_secs(10);
+/// Most misses cached at once, expired ones are dropped to make room.
+const TOKEN_MISS_LIMIT: usize = 1024;
+const TOKEN_EXPIRED_REASON: &str = "Session expired";
+const LOGGED_OUT_REASON: &str = "Logged out";
 
 #[derive(Debug)]
 pub struct IsTokenValid(pub String);
@@ -17,36 +35,186 @@ impl Message<IsTokenValid> for Tokens {
         msg: IsTokenValid,
         _ctx: &mut Context<Self, Self::Reply>,
     ) -> impl Future<Output = Self::Reply> + Send {
-        async move { self.is_valid(&msg.0) }
+        async move { self.resolve(&msg.0).await.is_some() }
+    }
+}
+
+#[derive(Debug)]
+pub struct ResolveToken(pub String);
+
+impl Message<ResolveToken> for Tokens {
+    type Reply = Option<User>;
+
+    fn handle(
+        &mut self,
+        msg: ResolveToken,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move { self.resolve(&msg.0).await }
+    }
+}
+
+/// Removes tokens past their expiry, revokes their sessions and publishes a `TokenExpired` event
+/// for each to the other subscribers. Also deletes them from the login database.
+#[derive(Debug)]
+pub struct SweepExpiredTokens;
+
+impl Message<SweepExpiredTokens> for Tokens {
+    type Reply = ();
+
+    fn handle(
+        &mut self,
+        _msg: SweepExpiredTokens,
+        _ctx: &mut Context<Self, Self::Reply>,
+    ) -> impl Future<Output = Self::Reply> + Send {
+        async move {
+            if let Err(e) = auth::clean_expired_tokens(&self.pool).await {
+                error!("Failed to delete expired tokens from database: {:?}", e);
+            }
+            let now = Instant::now();
+            self.misses.retain(|_, until| *until > now);
+            for token in self.remove_expired(Utc::now()) {
+                info!("Token expired: {}", token);
+                self.revoke_sessions(token.clone(), TOKEN_EXPIRED_REASON)
+                    .await;
+                let event = AuthenticationEvent::token_expired_event(token);
+                if let Err(e) = self.auth_pub_sub_ref.tell(Publish(event)).await {
+                    warn!("Failed to publish token expired event: {:?}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Token known to be valid, cached until `cached_until` when it's looked up in the database again.
+#[derive(Debug, Clone)]
+pub struct CachedToken {
+    pub user: User,
+    pub expires_at: DateTime<Utc>,
+    pub cached_until: Instant,
+}
+
+impl CachedToken {
+    fn new(token: &Token, user: User) -> Self {
+        Self {
+            user,
+            expires_at: token.expires_at,
+            cached_until: Instant::now() + TOKEN_CACHE_TTL,
+        }
     }
 }
 
-#[derive(Default, Debug)]
+/// Cache of the tokens in the login database.
+#[derive(Debug)]
 pub struct Tokens {
-    pub tokens: HashSet<String>,
-    pub token_to_user: std::collections::HashMap<String, User>,
+    pub tokens: HashMap<String, CachedToken>,
+    /// Tokens the database didn't know, rejected until the instant without another lookup.
+    misses: HashMap<String, Instant>,
+    pool: SqlitePool,
+    auth_pub_sub_ref: ActorRef<PubSub<AuthenticationEvent>>,
+    sessions_ref: ActorRef<SessionRegistry>,
 }
 
 impl Tokens {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(
+        pool: SqlitePool,
+        auth_pub_sub_ref: ActorRef<PubSub<AuthenticationEvent>>,
+        sessions_ref: ActorRef<SessionRegistry>,
+    ) -> Self {
+        Self {
+            tokens: HashMap::new(),
+            misses: HashMap::new(),
+            pool,
+            auth_pub_sub_ref,
+            sessions_ref,
+        }
     }
-    pub fn add_token(&mut self, token: String, user: User) {
-        self.tokens.insert(token.clone());
-        self.token_to_user.insert(token, user);
+
+    pub fn add_token(&mut self, token: Token, user: User) {
+        self.misses.remove(&token.token);
+        let cached = CachedToken::new(&token, user);
+        self.tokens.insert(token.token, cached);
     }
 
     pub fn remove_token(&mut self, token: &str) {
         self.tokens.remove(token);
-        self.token_to_user.remove(token);
     }
 
     pub fn is_valid(&self, token: &str) -> bool {
-        self.tokens.contains(token)
+        self.get_user(token).is_some()
     }
 
+    /// User of the token if it's cached, not expired and not due for a database lookup.
     pub fn get_user(&self, token: &str) -> Option<&User> {
-        self.token_to_user.get(token)
+        self.tokens
+            .get(token)
+            .filter(|cached| cached.expires_at > Utc::now() && cached.cached_until > Instant::now())
+            .map(|cached| &cached.user)
+    }
+
+    /// Resolves the token from the cache, falling back to the login database on a miss.
+    async fn resolve(&mut self, token: &str) -> Option<User> {
+        if let Some(user) = self.get_user(token) {
+            return Some(user.clone());
+        }
+        let now = Instant::now();
+        if self.misses.get(token).is_some_and(|until| *until > now) {
+            return None;
+        }
+        match auth::validate_token(&self.pool, token).await {
+            Ok(Some((user, db_token))) => {
+                self.add_token(db_token, user.clone());
+                Some(user)
+            }
+            Ok(None) => {
+                self.remove_token(token);
+                if self.misses.len() >= TOKEN_MISS_LIMIT {
+                    self.misses.retain(|_, until| *until > now);
+                }
+                if self.misses.len() < TOKEN_MISS_LIMIT {
+                    self.misses.insert(token.to_string(), now + TOKEN_MISS_TTL);
+                }
+                None
+            }
+            Err(e) => {
+                error!("Failed to look up token in database: {:?}", e);
+                None
+            }
+        }
+    }
+
+    async fn load_active_tokens(&mut self) {
+        match auth::get_active_tokens(&self.pool).await {
+            Ok(active_tokens) => {
+                for (user, token) in active_tokens {
+                    self.add_token(token, user);
+                }
+            }
+            Err(e) => error!("Failed to load active tokens: {:?}", e),
+        }
+    }
+
+    fn remove_expired(&mut self, now: DateTime<Utc>) -> Vec<String> {
+        let expired = self
+            .tokens
+            .iter()
+            .filter(|(_, cached)| cached.expires_at <= now)
+            .map(|(token, _)| token.clone())
+            .collect::<Vec<_>>();
+        for token in &expired {
+            self.remove_token(token);
+        }
+        expired
+    }
+
+    async fn revoke_sessions(&self, token: String, reason: &str) {
+        let revoke = RevokeToken {
+            token,
+            reason: reason.to_string(),
+        };
+        if let Err(e) = self.sessions_ref.tell(revoke).await {
+            warn!("Failed to revoke sessions: {:?}", e);
+        }
     }
 }
 
@@ -55,10 +223,28 @@ impl Actor for Tokens {
     type Error = ();
 
     fn on_start(
-        args: Self::Args,
-        _actor_ref: ActorRef<Self>,
+        mut args: Self::Args,
+        actor_ref: ActorRef<Self>,
     ) -> impl Future<Output = std::result::Result<Self, Self::Error>> + Send {
-        async move { Ok(args) }
+        async move {
+            // Clients holding a valid token stay logged in across server restarts
+            args.load_active_tokens().await;
+
+            let weak_ref = actor_ref.downgrade();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(TOKEN_SWEEP_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    let Some(actor_ref) = weak_ref.upgrade() else {
+                        break;
+                    };
+                    if let Err(e) = actor_ref.tell(SweepExpiredTokens).await {
+                        warn!("Failed to sweep expired tokens: {:?}", e);
+                    }
+                }
+            });
+            Ok(args)
+        }
     }
 }
 
@@ -73,16 +259,23 @@ impl Message<AuthenticationEvent> for Tokens {
         async move {
             match msg.event_type {
                 AuthEventType::Login(data) => {
-                    self.add_token(data.token.token, data.user.clone());
+                    self.add_token(data.token, data.user.clone());
                     println!("Token added for user: {:?}", data.user);
                 }
                 AuthEventType::Logout(data) => {
                     self.remove_token(&data.token);
                     println!("Token removed: {}", data.token);
+                    self.revoke_sessions(data.token, LOGGED_OUT_REASON).await;
                 }
                 AuthEventType::TokenExpired(data) => {
+                    // Our own sweep already revoked tokens it removed from the cache, and
+                    // sessions are only admitted with cached tokens
+                    if !self.tokens.contains_key(&data.token) {
+                        return;
+                    }
                     self.remove_token(&data.token);
                     println!("Token expired: {}", data.token);
+                    self.revoke_sessions(data.token, TOKEN_EXPIRED_REASON).await;
                 }
             }
         }
@@ -9,20 +9,41 @@ impl Plugin for ReplicateRulesPlugin {
         app.replicate::<Player>()
             .replicate::<Transform>()
             .replicate::<Backpack>()
-            .replicate::<HackingTool>()
+            .replicate::<ItemId>()
+            .replicate::<Quantity>()
+            .replicate::<Inventory>()
             .replicate::<Health>()
-            .replicate::<CreatureName>();
+            .replicate::<CreatureName>()
+            .replicate::<PlayerFactionInfo>()
+            .replicate::<OwnershipRegistry>()
+            .replicate::<SecurityLevel>()
+            .replicate::<SceneNodeName>()
+            .replicate::<DoorState>()
+            .replicate::<EnergyNodeState>()
+            .replicate::<ColonyController>();
 
         // Register client->server triggers
         app.add_client_trigger::<PlayerSpawnClientCommand>(Channel::Unordered);
+        app.add_client_trigger::<PlayerMoveClientCommand>(Channel::Unreliable);
 
-        app.add_client_trigger::<DoorHackCommand>(Channel::Unordered);
-        app.add_client_trigger::<LootCommand>(Channel::Unordered);
+        app.add_mapped_client_trigger::<UseRequest>(Channel::Unordered);
+        app.add_mapped_client_trigger::<LootCommand>(Channel::Unordered);
+        app.add_mapped_client_trigger::<SplitStackRequest>(Channel::Unordered);
+        app.add_mapped_client_trigger::<MergeStackRequest>(Channel::Unordered);
         app.add_client_trigger::<KillMeCommand>(Channel::Unordered);
+        app.add_client_trigger::<FireRequest>(Channel::Unordered);
+        app.add_client_trigger::<ColonyTravelClientCommand>(Channel::Unordered);
 
         // Register server->client triggers
         app.add_server_trigger::<DoorHackedEvent>(Channel::Unordered);
+        app.add_server_trigger::<ChannelProgress>(Channel::Ordered);
+        app.add_server_trigger::<ChannelEnded>(Channel::Ordered);
+        app.add_server_trigger::<RankUpEvent>(Channel::Unordered);
+        app.add_mapped_server_trigger::<HitConfirmed>(Channel::Unordered);
+        app.add_mapped_server_trigger::<LootContents>(Channel::Unordered);
+        app.add_mapped_server_trigger::<LootResult>(Channel::Unordered);
+        app.add_mapped_server_trigger::<InventoryRejected>(Channel::Unordered);
         app.add_server_trigger::<SetupPlayerServerCommand>(Channel::Unordered);
-        app.add_server_trigger::<SendDeadPlayerToCloningCommand>(Channel::Unordered);
+        app.add_server_trigger::<TransferToColony>(Channel::Unordered);
     }
 }
@@ -1,7 +1,17 @@
 use bevy::prelude::Component;
 use std::fmt::{Display, Formatter};
 
-#[derive(Component, Debug)]
+/// Id of the authenticated account a client session belongs to.
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct UserId(pub i64);
+
+impl Display for UserId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Component, Debug, Clone)]
 pub struct Username(pub String);
 
 impl Display for Username {
@@ -1,8 +1,9 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Component, Event, Reflect};
 use serde::{Deserialize, Serialize};
 
 #[derive(
     Component,
+    Reflect,
     Serialize,
     Deserialize,
     strum_macros::EnumString,
@@ -29,3 +30,18 @@ impl Colony {
         *self == Colony::StarMap
     }
 }
+
+/// A trigger from server that orders the client to connect to another colony.
+///
+/// The target colony admits the client only with the `ticket`.
+#[derive(Event, Serialize, Deserialize, Clone, Debug)]
+pub struct TransferToColony {
+    pub colony: Colony,
+    pub ticket: String,
+}
+
+/// Asks the server to move the player to another colony, e.g. when picked on the star map.
+#[derive(Event, Serialize, Deserialize, Clone, Debug)]
+pub struct ColonyTravelClientCommand {
+    pub colony: Colony,
+}
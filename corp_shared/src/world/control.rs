@@ -0,0 +1,182 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Faction that runs the colony, the one owning most of its energy nodes.
+///
+/// Energy nodes are the only structures factions capture. Doors and door terminals are key
+/// structures too, but they belong to the controlling faction, so they don't count towards control.
+#[derive(Resource, Default, Eq, PartialEq, Clone, Debug)]
+pub struct ColonyControl {
+    pub controller: Option<Faction>,
+    /// Energy nodes owned per faction.
+    pub nodes: Vec<(Faction, usize)>,
+}
+
+/// Controller of a colony as shown on the star map.
+#[derive(Component, Serialize, Deserialize, Eq, PartialEq, Clone, Debug)]
+pub struct ColonyController {
+    pub colony: Colony,
+    pub controller: Option<Faction>,
+}
+
+pub fn update_colony_control_system(
+    q_node: Query<&OwnershipRegistry, With<EnergyNode>>,
+    mut control: ResMut<ColonyControl>,
+) {
+    let mut nodes: Vec<(Faction, usize)> = Vec::new();
+    for owner in q_node.iter().filter_map(OwnershipRegistry::permanent_owner) {
+        match nodes.iter_mut().find(|(faction, _)| *faction == owner) {
+            Some((_, count)) => *count += 1,
+            None => nodes.push((owner, 1)),
+        }
+    }
+    if nodes == control.nodes {
+        return;
+    }
+
+    let most = nodes
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or_default();
+    let leaders = nodes
+        .iter()
+        .filter(|(_, count)| *count == most)
+        .map(|(faction, _)| *faction)
+        .collect::<Vec<_>>();
+    // On a tie the current controller holds on to the colony
+    let controller = match leaders.as_slice() {
+        [leader] => Some(*leader),
+        _ if control
+            .controller
+            .is_some_and(|controller| leaders.contains(&controller)) =>
+        {
+            control.controller
+        }
+        _ => None,
+    };
+    if controller != control.controller {
+        info!(
+            "Colony control changed from {:?} to {:?}",
+            control.controller, controller
+        );
+    }
+    *control = ColonyControl { controller, nodes };
+}
+
+pub fn apply_colony_control_system(
+    control: Res<ColonyControl>,
+    mut q_owned: Query<&mut OwnershipRegistry, Or<(With<Door>, With<DoorTerminal>)>>,
+) {
+    for mut registry in &mut q_owned {
+        if !control.is_changed() && !registry.is_added() {
+            continue;
+        }
+        if registry.permanent_owner() == control.controller {
+            continue;
+        }
+        match control.controller {
+            Some(controller) => registry.set_permanent(controller),
+            None => registry.remove_permanent(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faction_with_most_nodes_controls_colony() {
+        // given
+        let mut app = setup();
+        setup_energy_node(&mut app, Faction::EC);
+        setup_energy_node(&mut app, Faction::CMG);
+        setup_energy_node(&mut app, Faction::CMG);
+
+        // when
+        app.update();
+
+        // then
+        let control = app.get_resource::<ColonyControl>();
+        assert_eq!(control.controller, Some(Faction::CMG));
+    }
+
+    #[test]
+    fn tie_keeps_controller() {
+        // given
+        let mut app = setup();
+        setup_energy_node(&mut app, Faction::EC);
+        setup_energy_node(&mut app, Faction::EC);
+        setup_energy_node(&mut app, Faction::CMG);
+        let e_node = setup_energy_node(&mut app, Faction::VI);
+        app.update();
+
+        // when
+        app.get_mut::<OwnershipRegistry>(e_node)
+            .set_permanent(Faction::CMG);
+        app.update();
+
+        // then
+        let control = app.get_resource::<ColonyControl>();
+        assert_eq!(control.controller, Some(Faction::EC));
+    }
+
+    #[test]
+    fn doors_belong_to_controller() {
+        // given
+        let mut app = setup();
+        let e_node = setup_energy_node(&mut app, Faction::EC);
+        let e_door = app.world_mut().spawn(Door).id();
+        app.update();
+
+        // when
+        app.get_mut::<OwnershipRegistry>(e_node)
+            .set_permanent(Faction::VI);
+        app.update();
+
+        // then
+        let registry = app.get::<OwnershipRegistry>(e_door);
+        assert_eq!(registry.permanent_owner(), Some(Faction::VI));
+        assert!(registry.get_control_type(&Faction::EC).is_none());
+    }
+
+    #[test]
+    fn door_terminals_belong_to_controller() {
+        // given
+        let mut app = setup();
+        let e_node = setup_energy_node(&mut app, Faction::EC);
+        app.update();
+        let e_terminal = app.world_mut().spawn(DoorTerminal).id();
+        app.update();
+        assert_eq!(
+            app.get::<OwnershipRegistry>(e_terminal).permanent_owner(),
+            Some(Faction::EC)
+        );
+
+        // when
+        app.get_mut::<OwnershipRegistry>(e_node)
+            .set_permanent(Faction::CMG);
+        app.update();
+
+        // then
+        let registry = app.get::<OwnershipRegistry>(e_terminal);
+        assert_eq!(registry.permanent_owner(), Some(Faction::CMG));
+    }
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.init_resource::<ColonyControl>().add_systems(
+            Update,
+            (update_colony_control_system, apply_colony_control_system).chain(),
+        );
+        app
+    }
+
+    fn setup_energy_node(app: &mut App, faction: Faction) -> Entity {
+        app.world_mut()
+            .spawn((EnergyNode, OwnershipRegistry::new_permanent(faction)))
+            .id()
+    }
+}
@@ -4,8 +4,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Component)]
 pub struct Dead;
 
-#[derive(Event, Deserialize, Serialize, Clone, Debug)]
-pub struct SendDeadPlayerToCloningCommand;
+/// Player that landed the killing blow.
+#[derive(Component, Deref, Copy, Clone, Debug)]
+pub struct KilledBy(pub Entity);
 
 #[derive(Event, Deserialize, Serialize, Clone, Debug)]
 pub struct KillMeCommand;
@@ -1,13 +1,39 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(
+    Reflect,
+    Serialize,
+    Deserialize,
+    strum_macros::EnumString,
+    strum_macros::Display,
+    Eq,
+    PartialEq,
+    Copy,
+    Clone,
+    Debug,
+)]
 pub enum Faction {
     EC,
     CMG,
     VI,
 }
 
-#[derive(Default, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(
+    Reflect,
+    Serialize,
+    Deserialize,
+    strum_macros::EnumString,
+    strum_macros::Display,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Copy,
+    Clone,
+    Debug,
+)]
 pub enum Rank {
     #[default]
     R0,
@@ -20,15 +46,45 @@ pub enum Rank {
     R7,
 }
 
-#[derive(Component, Eq, PartialEq)]
+impl Rank {
+    pub const ALL: [Rank; 8] = [
+        Rank::R0,
+        Rank::R1,
+        Rank::R2,
+        Rank::R3,
+        Rank::R4,
+        Rank::R5,
+        Rank::R6,
+        Rank::R7,
+    ];
+}
+
+/// Faction and rank of a player, assigned by the server and replicated to clients.
+#[derive(Component, Serialize, Deserialize, Eq, PartialEq, Clone, Debug)]
 pub struct PlayerFactionInfo {
     pub faction: Faction,
     pub rank: Rank,
 }
 
+/// Hacked ownership is sent as the time it has left, clients count it down on their own.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub enum Ownership {
     Permanent(Faction),
-    Hacked(Faction, Timer),
+    Hacked(Faction, #[serde(with = "remaining_time")] Timer),
+}
+
+mod remaining_time {
+    use bevy::prelude::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(timer: &Timer, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(timer.remaining_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timer, D::Error> {
+        let remaining = f32::deserialize(deserializer)?;
+        Ok(Timer::from_seconds(remaining, TimerMode::Once))
+    }
 }
 
 impl Ownership {
@@ -40,7 +96,7 @@ impl Ownership {
     }
 }
 
-#[derive(Component, Default)]
+#[derive(Component, Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
 pub struct OwnershipRegistry {
     pub factions: Vec<Ownership>,
 }
@@ -59,11 +115,31 @@ impl OwnershipRegistry {
         self.factions.push(ownership);
     }
 
+    /// Faction that holds the structure regardless of hacks.
+    pub fn permanent_owner(&self) -> Option<Faction> {
+        self.factions.iter().find_map(|ownership| match ownership {
+            Ownership::Permanent(faction) => Some(*faction),
+            Ownership::Hacked(_, _) => None,
+        })
+    }
+
+    /// Hands the structure over to the faction, running hacks stay in place.
+    pub fn set_permanent(&mut self, faction: Faction) {
+        self.remove_permanent();
+        self.add_permanent(faction);
+    }
+
+    pub fn remove_permanent(&mut self) {
+        self.factions
+            .retain(|ownership| !matches!(ownership, Ownership::Permanent(_)));
+    }
+
     pub fn get_control_type(&self, faction: &Faction) -> Option<&Ownership> {
         self.factions.iter().find(|f| f.faction() == faction)
     }
 
-    pub fn process_temporary_factions(&mut self, time: &Time) {
+    /// Counts the hacks down, returns whether any of them ran out.
+    pub fn process_temporary_factions(&mut self, time: &Time) -> bool {
         // Update temporary factions
         for faction in self.factions.iter_mut() {
             if let Ownership::Hacked(_, timer) = faction {
@@ -72,6 +148,7 @@ impl OwnershipRegistry {
         }
 
         // Remove finished temporary factions
+        let count = self.factions.len();
         self.factions.retain(|faction| {
             if let Ownership::Hacked(_, timer) = faction {
                 !timer.finished()
@@ -79,6 +156,7 @@ impl OwnershipRegistry {
                 true
             }
         });
+        self.factions.len() != count
     }
 }
 
@@ -87,6 +165,12 @@ pub fn process_temporary_faction_ownership_timers_system(
     time: Res<Time>,
 ) {
     for mut faction_ownership_registry in &mut query {
-        faction_ownership_registry.process_temporary_factions(&time);
+        // Hacks count down on every side, only the ones running out are worth replicating
+        if faction_ownership_registry
+            .bypass_change_detection()
+            .process_temporary_factions(&time)
+        {
+            faction_ownership_registry.set_changed();
+        }
     }
 }
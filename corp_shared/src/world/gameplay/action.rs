@@ -1,4 +1,7 @@
-use crate::{prelude::Health, world::gameplay::roll::Range};
+use crate::{
+    prelude::{Health, SpawnProtection},
+    world::gameplay::roll::Range,
+};
 use bevy::prelude::*;
 use bevy_rand::prelude::{GlobalEntropy, WyRand};
 use rand::Rng;
@@ -13,8 +16,14 @@ pub struct HealActionEvent {
 pub struct DamageActionEvent {
     pub receiver: Entity,
     pub range: Range,
+    /// Player dealing the damage, `None` for the environment.
+    pub attacker: Option<Entity>,
 }
 
+/// Who dealt the latest damage, read when the receiver dies.
+#[derive(Component, Deref, Copy, Clone, Debug)]
+pub struct LastAttacker(pub Entity);
+
 pub struct ActionPlugin;
 
 impl Plugin for ActionPlugin {
@@ -36,11 +45,99 @@ fn heal(
 
 fn take_damage(
     trigger: Trigger<DamageActionEvent>,
-    mut q_health: Query<&mut Health>,
+    mut q_health: Query<&mut Health, Without<SpawnProtection>>,
     mut rng: GlobalEntropy<WyRand>,
+    mut commands: Commands,
 ) {
     if let Ok(mut health) = q_health.get_mut(trigger.receiver) {
         let amount = rng.gen_range((*trigger.range).clone()) as f32;
         health.take_damage(amount);
+        match trigger.attacker {
+            Some(attacker) => {
+                commands
+                    .entity(trigger.receiver)
+                    .insert(LastAttacker(attacker));
+            }
+            None => {
+                commands.entity(trigger.receiver).remove::<LastAttacker>();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use bevy_rand::plugin::EntropyPlugin;
+
+    #[test]
+    fn damage_records_attacker() {
+        // given
+        let mut app = setup();
+        let e_attacker = app.world_mut().spawn(Player).id();
+        let e_victim = app.world_mut().spawn((Player, Health::default())).id();
+
+        // when
+        damage(&mut app, e_victim, Some(e_attacker));
+
+        // then
+        assert!(app.get::<Health>(e_victim).get_health() < Health::default().get_health());
+        assert_eq!(app.get::<LastAttacker>(e_victim).0, e_attacker);
+    }
+
+    #[test]
+    fn environment_damage_clears_attacker() {
+        // given
+        let mut app = setup();
+        let e_attacker = app.world_mut().spawn(Player).id();
+        let e_victim = app.world_mut().spawn((Player, Health::default())).id();
+        damage(&mut app, e_victim, Some(e_attacker));
+
+        // when
+        damage(&mut app, e_victim, None);
+
+        // then
+        assert!(!app.has_component::<LastAttacker>(e_victim));
+    }
+
+    #[test]
+    fn protected_player_keeps_no_attacker() {
+        // given
+        let mut app = setup();
+        let e_attacker = app.world_mut().spawn(Player).id();
+        let e_victim = app
+            .world_mut()
+            .spawn((
+                Player,
+                Health::default(),
+                SpawnProtection(Timer::from_seconds(5.0, TimerMode::Once)),
+            ))
+            .id();
+
+        // when
+        damage(&mut app, e_victim, Some(e_attacker));
+
+        // then
+        assert_eq!(
+            app.get::<Health>(e_victim).get_health(),
+            Health::default().get_health()
+        );
+        assert!(!app.has_component::<LastAttacker>(e_victim));
+    }
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins((EntropyPlugin::<WyRand>::default(), ActionPlugin));
+        app
+    }
+
+    fn damage(app: &mut App, receiver: Entity, attacker: Option<Entity>) {
+        app.world_mut().trigger(DamageActionEvent {
+            receiver,
+            range: Range::new(10, 20),
+            attacker,
+        });
+        app.update();
     }
 }
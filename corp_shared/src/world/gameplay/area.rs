@@ -54,6 +54,7 @@ fn fire_area_collider(
                 commands.trigger(DamageActionEvent {
                     receiver: entity,
                     range: Range::new(8, 16),
+                    attacker: None,
                 });
                 // Match all intersections, not just the first one
                 true
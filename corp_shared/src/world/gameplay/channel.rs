@@ -0,0 +1,267 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How far a channeling creature may drift before the action counts as interrupted.
+const CHANNEL_MOVE_TOLERANCE: f32 = 0.3;
+
+/// Actions that take time to perform.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ChannelAction {
+    HackDoor,
+    /// Taking items out of a backpack.
+    Loot,
+    /// Using a structure with a [`UseDuration`].
+    Use,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ChannelCancelReason {
+    Moved,
+    Damaged,
+    Immobilized,
+}
+
+/// Action the entity is performing on the target, completes once the timer finishes.
+///
+/// Moving, taking damage or getting [`Immobilized`] cancels it.
+#[derive(Component, Debug)]
+pub struct Channeling {
+    pub action: ChannelAction,
+    pub target: Entity,
+    timer: Timer,
+    origin: Option<Vec3>,
+    hit_points: Option<f32>,
+}
+
+impl Channeling {
+    pub fn new(action: ChannelAction, target: Entity, duration: Duration) -> Self {
+        Self {
+            action,
+            target,
+            timer: Timer::new(duration, TimerMode::Once),
+            origin: None,
+            hit_points: None,
+        }
+    }
+
+    /// Share of the action done, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        self.timer.fraction()
+    }
+}
+
+/// Triggered on the channeling entity when its action finished.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ChannelCompleted {
+    pub action: ChannelAction,
+    pub target: Entity,
+}
+
+/// Triggered on the channeling entity when its action got interrupted.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ChannelCancelled {
+    pub action: ChannelAction,
+    pub target: Entity,
+    pub reason: ChannelCancelReason,
+}
+
+/// Progress of the action the local player is channeling, sent by the server.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ChannelProgress {
+    pub action: ChannelAction,
+    pub progress: f32,
+}
+
+/// Sent by the server when the action of the local player completed or got cancelled.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ChannelEnded {
+    pub action: ChannelAction,
+    pub cancelled: Option<ChannelCancelReason>,
+}
+
+pub struct ChannelPlugin;
+
+impl Plugin for ChannelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, channel_system);
+    }
+}
+
+pub fn channel_system(
+    mut q_channeling: Query<(
+        Entity,
+        &mut Channeling,
+        Option<&Transform>,
+        Option<&Health>,
+        Has<Immobilized>,
+    )>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut channeling, transform, health, immobilized) in &mut q_channeling {
+        let translation = transform.map(|transform| transform.translation);
+        let hit_points = health.map(Health::get_health);
+        let origin = *channeling
+            .origin
+            .get_or_insert(translation.unwrap_or_default());
+        let start_hit_points = *channeling
+            .hit_points
+            .get_or_insert(hit_points.unwrap_or_default());
+
+        let reason = if immobilized {
+            Some(ChannelCancelReason::Immobilized)
+        } else if hit_points.is_some_and(|hit_points| hit_points < start_hit_points) {
+            Some(ChannelCancelReason::Damaged)
+        } else if translation
+            .is_some_and(|translation| translation.distance(origin) > CHANNEL_MOVE_TOLERANCE)
+        {
+            Some(ChannelCancelReason::Moved)
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            commands.entity(entity).remove::<Channeling>();
+            commands.trigger_targets(
+                ChannelCancelled {
+                    action: channeling.action,
+                    target: channeling.target,
+                    reason,
+                },
+                entity,
+            );
+        } else if channeling.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Channeling>();
+            commands.trigger_targets(
+                ChannelCompleted {
+                    action: channeling.action,
+                    target: channeling.target,
+                },
+                entity,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Outcome {
+        completed: bool,
+        cancelled: Option<ChannelCancelReason>,
+    }
+
+    #[test]
+    fn channel_completes_after_duration() {
+        // given
+        let (mut app, e_player) = setup();
+
+        // when
+        app.update_after(Duration::from_secs_f32(1.0));
+        app.update_after(Duration::from_secs_f32(1.0));
+
+        // then
+        assert!(app.get_resource::<Outcome>().completed);
+        assert!(!app.has_component::<Channeling>(e_player));
+    }
+
+    #[test]
+    fn channel_in_progress_before_duration() {
+        // given
+        let (mut app, e_player) = setup();
+
+        // when
+        app.update_after(Duration::from_secs_f32(0.5));
+
+        // then
+        assert!(!app.get_resource::<Outcome>().completed);
+        assert_eq!(app.get::<Channeling>(e_player).progress(), 0.25);
+    }
+
+    #[test]
+    fn moving_cancels_channel() {
+        // given
+        let (mut app, e_player) = setup();
+        app.update();
+
+        // when
+        app.get_mut::<Transform>(e_player).translation = Vec3::X;
+        app.update();
+
+        // then
+        let outcome = app.get_resource::<Outcome>();
+        assert_eq!(outcome.cancelled, Some(ChannelCancelReason::Moved));
+        assert!(!outcome.completed);
+        assert!(!app.has_component::<Channeling>(e_player));
+    }
+
+    #[test]
+    fn damage_cancels_channel() {
+        // given
+        let (mut app, e_player) = setup();
+        app.update();
+
+        // when
+        app.get_mut::<Health>(e_player).take_damage(10.0);
+        app.update();
+
+        // then
+        assert_eq!(
+            app.get_resource::<Outcome>().cancelled,
+            Some(ChannelCancelReason::Damaged)
+        );
+    }
+
+    #[test]
+    fn immobilized_cancels_channel() {
+        // given
+        let (mut app, e_player) = setup();
+        app.update();
+
+        // when
+        app.world_mut().entity_mut(e_player).insert(Immobilized);
+        app.update();
+
+        // then
+        assert_eq!(
+            app.get_resource::<Outcome>().cancelled,
+            Some(ChannelCancelReason::Immobilized)
+        );
+    }
+
+    fn setup() -> (App, Entity) {
+        let mut app = App::new();
+        app.init_time();
+        app.init_resource::<Outcome>();
+        app.add_systems(Update, channel_system);
+        app.add_observer(
+            |_trigger: Trigger<ChannelCompleted>, mut outcome: ResMut<Outcome>| {
+                outcome.completed = true;
+            },
+        );
+        app.add_observer(
+            |trigger: Trigger<ChannelCancelled>, mut outcome: ResMut<Outcome>| {
+                outcome.cancelled = Some(trigger.event().reason);
+            },
+        );
+        let e_target = app.world_mut().spawn_empty().id();
+        let e_player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                Health::default(),
+                Channeling::new(
+                    ChannelAction::HackDoor,
+                    e_target,
+                    Duration::from_secs_f32(2.0),
+                ),
+            ))
+            .id();
+        (app, e_player)
+    }
+}
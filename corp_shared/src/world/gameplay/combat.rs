@@ -0,0 +1,41 @@
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How far a shot travels before it misses.
+pub const WEAPON_RANGE: f32 = 30.0;
+/// Time between two shots of the same player.
+pub const FIRE_COOLDOWN: Duration = Duration::from_millis(400);
+/// Damage of a single hit, rolled between the two values.
+pub const WEAPON_DAMAGE: (u32, u32) = (10, 20);
+
+/// Asks the server to fire the player's weapon, the server decides what gets hit.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct FireRequest {
+    /// Where the player aims, doesn't need to be normalized.
+    pub direction: Vec3,
+}
+
+/// Sent by the server to both the shooter and the victim of a hit.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct HitConfirmed {
+    pub shooter: Entity,
+    pub victim: Entity,
+    /// Spawn protection of the victim absorbed the hit.
+    pub blocked: bool,
+}
+
+impl MapEntities for HitConfirmed {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.shooter = entity_mapper.get_mapped(self.shooter);
+        self.victim = entity_mapper.get_mapped(self.victim);
+    }
+}
+
+/// Shots travel level with the ground, aiming up or down only changes where the player looks.
+pub fn aim_direction(direction: Vec3) -> Option<Dir3> {
+    Dir3::new(direction.with_y(0.0)).ok()
+}
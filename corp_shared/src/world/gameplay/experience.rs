@@ -0,0 +1,214 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Experience points a player has earned.
+#[derive(Component, Serialize, Deserialize, Default, Deref, Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Experience(pub u64);
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ExperienceSource {
+    Hack,
+    Capture,
+    Kill,
+    Delivery,
+}
+
+/// How much experience each deed is worth and how much each rank takes.
+#[derive(Resource, Clone, Debug)]
+pub struct ProgressionConfig {
+    pub hack: u64,
+    pub capture: u64,
+    pub kill: u64,
+    pub delivery: u64,
+    /// Experience needed for each rank, starting with [`Rank::R1`].
+    pub rank_thresholds: [u64; 7],
+}
+
+impl Default for ProgressionConfig {
+    fn default() -> Self {
+        Self {
+            hack: 50,
+            capture: 150,
+            kill: 100,
+            delivery: 75,
+            rank_thresholds: [100, 300, 600, 1000, 1600, 2500, 4000],
+        }
+    }
+}
+
+impl ProgressionConfig {
+    pub fn award(&self, source: ExperienceSource) -> u64 {
+        match source {
+            ExperienceSource::Hack => self.hack,
+            ExperienceSource::Capture => self.capture,
+            ExperienceSource::Kill => self.kill,
+            ExperienceSource::Delivery => self.delivery,
+        }
+    }
+
+    /// Highest rank the experience is enough for.
+    pub fn rank_for(&self, experience: u64) -> Rank {
+        let reached = self
+            .rank_thresholds
+            .iter()
+            .take_while(|threshold| experience >= **threshold)
+            .count();
+        Rank::ALL[reached]
+    }
+}
+
+/// Triggered on a player to reward it.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AwardExperience {
+    pub source: ExperienceSource,
+}
+
+/// Sent by the server to the player that got promoted.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct RankUpEvent {
+    pub rank: Rank,
+    pub experience: u64,
+}
+
+pub struct ProgressionPlugin;
+
+impl Plugin for ProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProgressionConfig>()
+            .add_observer(on_award_experience);
+    }
+}
+
+/// Adds the experience and promotes the player once it reaches the next rank.
+///
+/// Players are never demoted, ranks given by other means stay.
+pub fn on_award_experience(
+    trigger: Trigger<AwardExperience>,
+    config: Res<ProgressionConfig>,
+    mut q_player: Query<(&mut Experience, &mut PlayerFactionInfo)>,
+    mut commands: Commands,
+) {
+    let player_e = trigger.target();
+    let Ok((mut experience, mut faction_info)) = q_player.get_mut(player_e) else {
+        return;
+    };
+    experience.0 += config.award(trigger.event().source);
+
+    let rank = config.rank_for(experience.0);
+    if rank > faction_info.rank {
+        faction_info.rank = rank;
+        commands.trigger_targets(
+            RankUpEvent {
+                rank,
+                experience: experience.0,
+            },
+            player_e,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn award_adds_experience() {
+        // given
+        let mut app = setup();
+        let e_player = setup_player(&mut app, 0, Rank::R0);
+
+        // when
+        award(&mut app, e_player, ExperienceSource::Hack);
+
+        // then
+        assert_eq!(app.get::<Experience>(e_player).0, 50);
+        assert_eq!(app.get::<PlayerFactionInfo>(e_player).rank, Rank::R0);
+    }
+
+    #[test]
+    fn reaching_threshold_promotes_player() {
+        // given
+        let mut app = setup();
+        let e_player = setup_player(&mut app, 950, Rank::R3);
+
+        // when
+        award(&mut app, e_player, ExperienceSource::Hack);
+
+        // then
+        assert_eq!(app.get::<PlayerFactionInfo>(e_player).rank, Rank::R4);
+        assert_eq!(app.get_resource::<RankUps>().0, vec![Rank::R4]);
+    }
+
+    #[test]
+    fn players_are_not_demoted() {
+        // given
+        let mut app = setup();
+        let e_player = setup_player(&mut app, 0, Rank::R6);
+
+        // when
+        award(&mut app, e_player, ExperienceSource::Capture);
+
+        // then
+        assert_eq!(app.get::<PlayerFactionInfo>(e_player).rank, Rank::R6);
+        assert!(app.get_resource::<RankUps>().0.is_empty());
+    }
+
+    #[test]
+    fn promotion_opens_doors() {
+        // given
+        let mut app = setup();
+        let e_player = setup_player(&mut app, 950, Rank::R3);
+        let level = SecurityLevel::Low;
+        assert!(!level.has_required_rank(&app.get::<PlayerFactionInfo>(e_player).rank));
+
+        // when
+        award(&mut app, e_player, ExperienceSource::Kill);
+
+        // then
+        assert!(level.has_required_rank(&app.get::<PlayerFactionInfo>(e_player).rank));
+    }
+
+    #[test]
+    fn rank_for_experience() {
+        let config = ProgressionConfig::default();
+        assert_eq!(config.rank_for(0), Rank::R0);
+        assert_eq!(config.rank_for(100), Rank::R1);
+        assert_eq!(config.rank_for(2499), Rank::R5);
+        assert_eq!(config.rank_for(u64::MAX), Rank::R7);
+    }
+
+    #[derive(Resource, Default)]
+    struct RankUps(Vec<Rank>);
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.add_plugins(ProgressionPlugin)
+            .init_resource::<RankUps>()
+            .add_observer(
+                |trigger: Trigger<RankUpEvent>, mut rank_ups: ResMut<RankUps>| {
+                    rank_ups.0.push(trigger.event().rank);
+                },
+            );
+        app
+    }
+
+    fn setup_player(app: &mut App, experience: u64, rank: Rank) -> Entity {
+        app.world_mut()
+            .spawn((
+                Player,
+                Experience(experience),
+                PlayerFactionInfo {
+                    faction: Faction::EC,
+                    rank,
+                },
+            ))
+            .id()
+    }
+
+    fn award(app: &mut App, player: Entity, source: ExperienceSource) {
+        app.world_mut()
+            .trigger_targets(AwardExperience { source }, player);
+        app.update();
+    }
+}
@@ -1,9 +1,15 @@
 pub mod action;
 pub mod area;
+pub mod channel;
+pub mod combat;
+pub mod experience;
 pub mod roll;
 pub mod tick;
 
 pub use action::*;
 pub use area::*;
+pub use channel::*;
+pub use combat::*;
+pub use experience::*;
 pub use roll::*;
 pub use tick::*;
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use bevy_rand::prelude::{Entropy, ForkableRng, GlobalEntropy, WyRand};
+use rand::Rng;
 use std::ops::RangeInclusive;
 
 #[derive(Component, Clone, Deref)]
@@ -9,3 +11,261 @@ impl Range {
         Self(min..=max)
     }
 }
+
+/// How the chance grows after each failed roll.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChanceGrowth {
+    Linear,
+    Sigmoid,
+    Exponential,
+    Capped { limit: f32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chance {
+    /// Probability of the first roll and of every roll after a success.
+    pub base: f32,
+    pub increase: f32,
+    pub growth: ChanceGrowth,
+}
+
+impl Default for Chance {
+    fn default() -> Self {
+        Self {
+            base: 0.1,
+            increase: 0.1,
+            growth: ChanceGrowth::Linear,
+        }
+    }
+}
+
+/// Pseudo-random distribution, every failed roll makes the next one more likely to succeed.
+///
+/// Keeps long streaks of failures and lucky runs away while matching the expected probability.
+/// The entity gets its own [`Entropy`] forked from the [`GlobalEntropy`].
+#[derive(Component, Clone, Debug)]
+pub struct PseudoRandom {
+    chance: Chance,
+    current: f32,
+}
+
+impl PseudoRandom {
+    pub fn new(chance: Chance) -> Self {
+        Self {
+            chance,
+            current: chance.base,
+        }
+    }
+
+    /// Probability of the next roll.
+    pub fn current_chance(&self) -> f32 {
+        self.current
+    }
+
+    pub fn roll(&mut self, rng: &mut impl Rng) -> bool {
+        let success = rng.r#gen::<f32>() < self.current;
+        if success {
+            self.current = self.chance.base;
+        } else {
+            self.current = match self.chance.growth {
+                ChanceGrowth::Linear => self.current + self.chance.increase,
+                ChanceGrowth::Sigmoid => {
+                    const STEEPNESS: f32 = 2.0;
+                    (2.0 - 2.0 * self.chance.base)
+                        * (1.0 / (1.0 + (-STEEPNESS * self.current).exp()))
+                        - (1.0 - 2.0 * self.chance.base)
+                }
+                ChanceGrowth::Exponential => self.current * 2.0,
+                ChanceGrowth::Capped { limit } => (self.current + self.chance.increase).min(limit),
+            }
+            .min(1.0);
+        }
+        success
+    }
+}
+
+pub struct RollPlugin;
+
+impl Plugin for RollPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(fork_entropy);
+    }
+}
+
+fn fork_entropy(
+    trigger: Trigger<OnAdd, PseudoRandom>,
+    q_entropy: Query<(), With<Entropy<WyRand>>>,
+    mut global: GlobalEntropy<WyRand>,
+    mut commands: Commands,
+) {
+    if !q_entropy.contains(trigger.target()) {
+        commands.entity(trigger.target()).insert(global.fork_rng());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::TestUtils;
+    use bevy_rand::plugin::EntropyPlugin;
+    use rand::{RngCore, SeedableRng};
+
+    const ROLLS: usize = 100;
+
+    /// Always produces the same number, the highest one fails every roll short of certain.
+    struct FixedRng(u32);
+
+    impl FixedRng {
+        const UNLUCKY: Self = Self(u32::MAX);
+    }
+
+    impl RngCore for FixedRng {
+        fn next_u32(&mut self) -> u32 {
+            self.0
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            (u64::from(self.0) << 32) | u64::from(self.0)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(self.0 as u8);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn success_resets_chance() {
+        // given
+        let mut rng = WyRand::seed_from_u64(0);
+        let mut prd = PseudoRandom::new(Chance {
+            base: 0.2,
+            ..default()
+        });
+
+        // when
+        let mut rolls = Vec::new();
+        for _ in 0..ROLLS {
+            rolls.push((prd.roll(&mut rng), prd.current_chance()));
+        }
+
+        // then
+        for (success, chance) in rolls {
+            if success {
+                assert_eq!(chance, 0.2);
+            } else {
+                assert!(chance > 0.2);
+            }
+        }
+    }
+
+    #[test]
+    fn failure_streak_is_limited() {
+        // given
+        let mut rng = WyRand::seed_from_u64(0);
+        let mut prd = PseudoRandom::new(Chance {
+            base: 0.2,
+            increase: 0.9,
+            growth: ChanceGrowth::Linear,
+        });
+
+        // when
+        let rolls = (0..ROLLS).map(|_| prd.roll(&mut rng)).collect::<Vec<_>>();
+
+        // then
+        assert!(rolls.windows(2).all(|pair| pair[0] || pair[1]));
+    }
+
+    #[test]
+    fn exponential_chance_doubles() {
+        // given
+        let mut prd = PseudoRandom::new(Chance {
+            base: 0.2,
+            growth: ChanceGrowth::Exponential,
+            ..default()
+        });
+
+        // when
+        prd.roll(&mut FixedRng::UNLUCKY);
+        let doubled = prd.current_chance();
+        prd.roll(&mut FixedRng::UNLUCKY);
+        prd.roll(&mut FixedRng::UNLUCKY);
+
+        // then
+        assert_eq!(doubled, 0.4);
+        assert_eq!(prd.current_chance(), 1.0);
+    }
+
+    #[test]
+    fn capped_chance_stays_under_limit() {
+        // given
+        let mut prd = PseudoRandom::new(Chance {
+            base: 0.0,
+            increase: 0.1,
+            growth: ChanceGrowth::Capped { limit: 0.5 },
+        });
+
+        // when
+        for _ in 0..ROLLS {
+            prd.roll(&mut FixedRng::UNLUCKY);
+        }
+
+        // then
+        assert_eq!(prd.current_chance(), 0.5);
+    }
+
+    #[test]
+    fn sigmoid_chance_grows() {
+        // given
+        let mut prd = PseudoRandom::new(Chance {
+            base: 0.2,
+            growth: ChanceGrowth::Sigmoid,
+            ..default()
+        });
+
+        // when
+        prd.roll(&mut FixedRng::UNLUCKY);
+
+        // then
+        assert!(prd.current_chance() > 0.2);
+        assert!(prd.current_chance() <= 1.0);
+    }
+
+    #[test]
+    fn certain_chance_always_succeeds() {
+        // given
+        let mut prd = PseudoRandom::new(Chance {
+            base: 1.0,
+            ..default()
+        });
+
+        // when
+        let successes = (0..ROLLS)
+            .filter(|_| prd.roll(&mut FixedRng::UNLUCKY))
+            .count();
+
+        // then
+        assert_eq!(successes, ROLLS);
+    }
+
+    #[test]
+    fn pseudo_random_gets_own_entropy() {
+        // given
+        let mut app = App::new();
+        app.add_plugins((EntropyPlugin::<WyRand>::default(), RollPlugin));
+
+        // when
+        let e_player = app
+            .world_mut()
+            .spawn(PseudoRandom::new(Chance::default()))
+            .id();
+        app.update();
+
+        // then
+        assert!(app.has_component::<Entropy<WyRand>>(e_player));
+    }
+}
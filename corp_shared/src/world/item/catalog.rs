@@ -0,0 +1,232 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
+
+/// Catalog file, relative to the asset directory of the client.
+pub const ITEM_CATALOG_PATH: &str = "items/catalog.ron";
+
+pub const HACKING_TOOL: &str = "hacking_tool";
+pub const ENERGY_CELL: &str = "energy_cell";
+
+/// Catalog entry an item is made from, the only part of an item that gets replicated.
+#[derive(Component, Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Debug)]
+#[require(Item)]
+#[cfg_attr(feature = "client", require(
+    StateScoped<crate::prelude::GameState> = StateScoped(crate::prelude::GameState::Playing))
+)]
+pub struct ItemId(pub String);
+
+impl ItemId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ItemCategory {
+    Tool,
+    Weapon,
+    Consumable,
+    Material,
+}
+
+/// Code backed behavior of an item, designers pick one from these.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ItemBehavior {
+    HackingTool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct ItemDefinition {
+    pub id: String,
+    pub name: String,
+    pub category: ItemCategory,
+    pub stack_size: u32,
+    pub weight: f32,
+    pub icon: String,
+    #[serde(default)]
+    pub behavior: Option<ItemBehavior>,
+    #[serde(default)]
+    pub stats: HashMap<String, f32>,
+}
+
+/// Free form stats of an item, copied from its catalog entry.
+#[derive(Component, Deref, PartialEq, Clone, Debug)]
+pub struct ItemStats(pub HashMap<String, f32>);
+
+/// All items that exist in the game keyed by id, loaded from [`ITEM_CATALOG_PATH`].
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ItemCatalog {
+    items: HashMap<String, ItemDefinition>,
+}
+
+impl ItemCatalog {
+    pub fn from_ron(ron: &str) -> anyhow::Result<Self> {
+        let mut items = HashMap::new();
+        for item in ron::from_str::<Vec<ItemDefinition>>(ron)? {
+            if let Some(duplicate) = items.insert(item.id.clone(), item) {
+                anyhow::bail!("item {} is defined more than once", duplicate.id);
+            }
+        }
+        Ok(Self { items })
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        Self::from_ron(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, id: &ItemId) -> Option<&ItemDefinition> {
+        self.items.get(&id.0)
+    }
+
+    pub fn items(&self) -> impl ExactSizeIterator<Item = &ItemDefinition> {
+        self.items.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Weight of one item, items outside the catalog weigh nothing.
+    pub fn weight(&self, id: Option<&ItemId>) -> f32 {
+        id.and_then(|id| self.get(id))
+            .map_or(0.0, |definition| definition.weight)
+    }
+
+    /// How many items fit one slot, items outside the catalog don't stack.
+    pub fn stack_size(&self, id: Option<&ItemId>) -> u32 {
+        id.and_then(|id| self.get(id))
+            .map_or(1, |definition| definition.stack_size.max(1))
+    }
+}
+
+/// Completes items as their id is inserted, on both sides of the network.
+///
+/// Each side inserts the [`ItemCatalog`] itself, the server reads it from its assets directory and
+/// the client loads it as an asset.
+pub struct ItemCatalogPlugin;
+
+impl Plugin for ItemCatalogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_insert_item_id);
+    }
+}
+
+pub fn on_insert_item_id(
+    trigger: Trigger<OnInsert, ItemId>,
+    q_item_id: Query<&ItemId>,
+    catalog: Res<ItemCatalog>,
+    mut commands: Commands,
+) {
+    let item_e = trigger.target();
+    let Ok(item_id) = q_item_id.get(item_e) else {
+        return;
+    };
+    let Some(definition) = catalog.get(item_id) else {
+        warn!("Item {} is not in the catalog", item_id.0);
+        commands.entity(item_e).insert(Name::new(item_id.0.clone()));
+        return;
+    };
+    let mut entity = commands.entity(item_e);
+    entity.insert(Name::new(definition.name.clone()));
+    if !definition.stats.is_empty() {
+        entity.insert(ItemStats(definition.stats.clone()));
+    }
+    if let Some(ItemBehavior::HackingTool) = definition.behavior {
+        entity.insert(HackingTool);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"[
+        (
+            id: "hacking_tool",
+            name: "Hacking Tool",
+            category: Tool,
+            stack_size: 1,
+            weight: 1.5,
+            icon: "items/icons/hacking_tool.png",
+            behavior: Some(HackingTool),
+        ),
+        (
+            id: "med_kit",
+            name: "Med Kit",
+            category: Consumable,
+            stack_size: 5,
+            weight: 0.5,
+            icon: "items/icons/med_kit.png",
+            stats: { "heal": 40.0 },
+        ),
+    ]"#;
+
+    #[test]
+    fn catalog_is_parsed() {
+        let catalog = ItemCatalog::from_ron(CATALOG).unwrap();
+
+        let med_kit = catalog.get(&ItemId::new("med_kit")).unwrap();
+        assert_eq!(med_kit.category, ItemCategory::Consumable);
+        assert_eq!(med_kit.stack_size, 5);
+        assert_eq!(med_kit.stats.get("heal"), Some(&40.0));
+        assert!(catalog.get(&ItemId::new("unknown")).is_none());
+    }
+
+    #[test]
+    fn item_gets_behavior_from_catalog() {
+        // given
+        let mut app = setup();
+
+        // when
+        let e_item = app.world_mut().spawn(ItemId::new(HACKING_TOOL)).id();
+        app.update();
+
+        // then
+        assert!(app.has_component::<HackingTool>(e_item));
+        assert!(app.has_component::<Item>(e_item));
+        assert_eq!(app.get::<Name>(e_item).as_str(), "Hacking Tool");
+    }
+
+    #[test]
+    fn item_gets_stats_from_catalog() {
+        // given
+        let mut app = setup();
+
+        // when
+        let e_item = app.world_mut().spawn(ItemId::new("med_kit")).id();
+        app.update();
+
+        // then
+        assert!(!app.has_component::<HackingTool>(e_item));
+        assert_eq!(app.get::<ItemStats>(e_item).get("heal"), Some(&40.0));
+    }
+
+    #[test]
+    fn duplicate_item_is_rejected() {
+        let ron = r#"[
+            (id: "ore", name: "Ore", category: Material, stack_size: 20, weight: 1.0, icon: ""),
+            (id: "ore", name: "Ore", category: Material, stack_size: 10, weight: 1.0, icon: ""),
+        ]"#;
+
+        assert!(ItemCatalog::from_ron(ron).is_err());
+    }
+
+    #[test]
+    fn shipped_catalog_is_valid() {
+        let catalog = ItemCatalog::from_ron(include_str!(
+            "../../../../corp_client/assets/items/catalog.ron"
+        ))
+        .unwrap();
+
+        assert!(catalog.get(&ItemId::new(HACKING_TOOL)).is_some());
+    }
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.insert_resource(ItemCatalog::from_ron(CATALOG).unwrap())
+            .add_observer(on_insert_item_id);
+        app
+    }
+}
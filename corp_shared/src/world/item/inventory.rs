@@ -1,4 +1,486 @@
-use bevy::prelude::*;
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+use bevy_replicon::prelude::Replicated;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Default, Clone, Debug, PartialEq)]
-pub struct Inventory;
+/// Slots and weight a container can hold, every stack of items takes up one slot.
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Inventory {
+    pub slots: usize,
+    pub max_weight: f32,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self {
+            slots: 12,
+            max_weight: 25.0,
+        }
+    }
+}
+
+impl Inventory {
+    pub fn fits(&self, load: InventoryLoad) -> bool {
+        load.slots <= self.slots && load.weight <= self.max_weight
+    }
+}
+
+/// Slots and weight taken up in a container.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct InventoryLoad {
+    pub slots: usize,
+    pub weight: f32,
+}
+
+impl InventoryLoad {
+    pub fn of<'a>(
+        items: impl IntoIterator<Item = (Option<&'a ItemId>, &'a Quantity)>,
+        catalog: &ItemCatalog,
+    ) -> Self {
+        items
+            .into_iter()
+            .fold(Self::default(), |load, (item_id, quantity)| Self {
+                slots: load.slots + 1,
+                weight: load.weight + catalog.weight(item_id) * quantity.0 as f32,
+            })
+    }
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum InventoryError {
+    NoFreeSlot,
+    TooHeavy,
+    /// Stacks hold different items or lie in different containers.
+    NotStackable,
+    /// Split more than the stack holds.
+    NotEnough,
+}
+
+/// Triggered on an item to move it into the container, stacks of the same item are filled first.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct TransferItem {
+    pub container: Entity,
+}
+
+/// Triggered on a stack to move the amount into a new stack in the same container.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SplitStack {
+    pub amount: u32,
+}
+
+/// Triggered on a stack to move as much of it as fits into the other stack.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct MergeStack {
+    pub into: Entity,
+}
+
+/// Triggered on the item an inventory operation was refused for.
+///
+/// Sent to the client when the container is its player.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct InventoryRejected {
+    pub container: Entity,
+    pub error: InventoryError,
+}
+
+impl MapEntities for InventoryRejected {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.container = entity_mapper.get_mapped(self.container);
+    }
+}
+
+/// Asks the server to split a stack in the inventory of the player.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct SplitStackRequest {
+    pub item: Entity,
+    pub amount: u32,
+}
+
+impl MapEntities for SplitStackRequest {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.item = entity_mapper.get_mapped(self.item);
+    }
+}
+
+/// Asks the server to merge two stacks in the inventory of the player.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct MergeStackRequest {
+    pub item: Entity,
+    pub into: Entity,
+}
+
+impl MapEntities for MergeStackRequest {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.item = entity_mapper.get_mapped(self.item);
+        self.into = entity_mapper.get_mapped(self.into);
+    }
+}
+
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_transfer_item)
+            .add_observer(on_split_stack)
+            .add_observer(on_merge_stack);
+    }
+}
+
+fn reject(commands: &mut Commands, item_e: Entity, container_e: Entity, error: InventoryError) {
+    info!("Item {} rejected by {}: {:?}", item_e, container_e, error);
+    commands.trigger_targets(
+        InventoryRejected {
+            container: container_e,
+            error,
+        },
+        item_e,
+    );
+}
+
+pub fn on_transfer_item(
+    trigger: Trigger<TransferItem>,
+    catalog: Res<ItemCatalog>,
+    q_inventory: Query<(&Inventory, Option<&Contains>)>,
+    mut q_item: Query<(Option<&ItemId>, &mut Quantity, Option<&StoredIn>)>,
+    mut commands: Commands,
+) {
+    let item_e = trigger.target();
+    let container_e = trigger.event().container;
+    let Ok((inventory, contains)) = q_inventory.get(container_e) else {
+        warn!(
+            "Tried to transfer {} into {} which has no inventory",
+            item_e, container_e
+        );
+        return;
+    };
+    let Ok((item_id, quantity, stored_in)) = q_item.get(item_e) else {
+        return;
+    };
+    if stored_in.is_some_and(|stored_in| stored_in.container() == container_e) {
+        return;
+    }
+    let item_id = item_id.cloned();
+    let mut remaining = quantity.0;
+
+    let stored = contains
+        .map(|contains| contains.to_vec())
+        .unwrap_or_default();
+    let load = InventoryLoad::of(
+        stored
+            .iter()
+            .filter_map(|stack_e| q_item.get(*stack_e).ok())
+            .map(|(stack_id, quantity, _)| (stack_id, quantity)),
+        &catalog,
+    );
+    if load.weight + catalog.weight(item_id.as_ref()) * remaining as f32 > inventory.max_weight {
+        reject(&mut commands, item_e, container_e, InventoryError::TooHeavy);
+        return;
+    }
+
+    let stack_size = catalog.stack_size(item_id.as_ref());
+    for stack_e in &stored {
+        let Ok((stack_id, mut stack_quantity, _)) = q_item.get_mut(*stack_e) else {
+            continue;
+        };
+        if remaining == 0 || stack_id != item_id.as_ref() {
+            continue;
+        }
+        let moved = remaining.min(stack_size.saturating_sub(stack_quantity.0));
+        if moved > 0 {
+            stack_quantity.0 += moved;
+            remaining -= moved;
+        }
+    }
+    if remaining == 0 {
+        commands.entity(item_e).despawn();
+        return;
+    }
+
+    // Whatever didn't fit into the stacks stays behind
+    if let Ok((_, mut quantity, _)) = q_item.get_mut(item_e) {
+        quantity.set_if_neq(Quantity(remaining));
+    }
+    if load.slots >= inventory.slots {
+        reject(
+            &mut commands,
+            item_e,
+            container_e,
+            InventoryError::NoFreeSlot,
+        );
+        return;
+    }
+    commands.entity(item_e).insert(StoredIn(container_e));
+}
+
+pub fn on_split_stack(
+    trigger: Trigger<SplitStack>,
+    q_inventory: Query<(&Inventory, &Contains)>,
+    mut q_item: Query<(Option<&ItemId>, &mut Quantity, &StoredIn, Has<Replicated>)>,
+    mut commands: Commands,
+) {
+    let item_e = trigger.target();
+    let amount = trigger.event().amount;
+    let Ok((item_id, mut quantity, stored_in, replicated)) = q_item.get_mut(item_e) else {
+        return;
+    };
+    let container_e = stored_in.container();
+    let Ok((inventory, contains)) = q_inventory.get(container_e) else {
+        return;
+    };
+    let Some(item_id) = item_id
+        .cloned()
+        .filter(|_| amount > 0 && amount < quantity.0)
+    else {
+        reject(
+            &mut commands,
+            item_e,
+            container_e,
+            InventoryError::NotEnough,
+        );
+        return;
+    };
+    if contains.len() >= inventory.slots {
+        reject(
+            &mut commands,
+            item_e,
+            container_e,
+            InventoryError::NoFreeSlot,
+        );
+        return;
+    }
+
+    quantity.0 -= amount;
+    let mut split = commands.spawn((item_id, Quantity(amount), StoredIn(container_e)));
+    if replicated {
+        split.insert(Replicated);
+    }
+}
+
+pub fn on_merge_stack(
+    trigger: Trigger<MergeStack>,
+    catalog: Res<ItemCatalog>,
+    mut q_item: Query<(Option<&ItemId>, &mut Quantity, &StoredIn)>,
+    mut commands: Commands,
+) {
+    let item_e = trigger.target();
+    let into_e = trigger.event().into;
+    let Ok([(item_id, mut quantity, stored_in), (into_id, mut into_quantity, into_stored_in)]) =
+        q_item.get_many_mut([item_e, into_e])
+    else {
+        return;
+    };
+    let container_e = stored_in.container();
+    if item_id.is_none() || item_id != into_id || container_e != into_stored_in.container() {
+        reject(
+            &mut commands,
+            item_e,
+            container_e,
+            InventoryError::NotStackable,
+        );
+        return;
+    }
+
+    let stack_size = catalog.stack_size(item_id);
+    let moved = quantity.0.min(stack_size.saturating_sub(into_quantity.0));
+    into_quantity.0 += moved;
+    quantity.0 -= moved;
+    if quantity.0 == 0 {
+        commands.entity(item_e).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"[
+        (
+            id: "energy_cell",
+            name: "Energy Cell",
+            category: Material,
+            stack_size: 20,
+            weight: 0.5,
+            icon: "items/icons/energy_cell.png",
+        ),
+        (
+            id: "pulse_rifle",
+            name: "Pulse Rifle",
+            category: Weapon,
+            stack_size: 1,
+            weight: 4.0,
+            icon: "items/icons/pulse_rifle.png",
+        ),
+    ]"#;
+
+    #[test]
+    fn transfer_stores_item() {
+        // given
+        let mut app = setup();
+        let e_player = setup_inventory(&mut app, 2, 10.0);
+        let e_item = setup_item(&mut app, "pulse_rifle", 1);
+
+        // when
+        transfer(&mut app, e_item, e_player);
+
+        // then
+        assert_eq!(app.get::<StoredIn>(e_item).container(), e_player);
+    }
+
+    #[test]
+    fn transfer_is_rejected_without_free_slot() {
+        // given
+        let mut app = setup();
+        let e_player = setup_inventory(&mut app, 1, 10.0);
+        let e_stored = setup_item(&mut app, "pulse_rifle", 1);
+        transfer(&mut app, e_stored, e_player);
+        let e_item = setup_item(&mut app, "energy_cell", 1);
+
+        // when
+        transfer(&mut app, e_item, e_player);
+
+        // then
+        assert!(!app.has_component::<StoredIn>(e_item));
+        assert_eq!(
+            app.get_resource::<Rejections>().0,
+            vec![InventoryError::NoFreeSlot]
+        );
+    }
+
+    #[test]
+    fn transfer_is_rejected_when_too_heavy() {
+        // given
+        let mut app = setup();
+        let e_player = setup_inventory(&mut app, 4, 6.0);
+        let e_stored = setup_item(&mut app, "pulse_rifle", 1);
+        transfer(&mut app, e_stored, e_player);
+        let e_item = setup_item(&mut app, "pulse_rifle", 1);
+
+        // when
+        transfer(&mut app, e_item, e_player);
+
+        // then
+        assert!(!app.has_component::<StoredIn>(e_item));
+        assert_eq!(
+            app.get_resource::<Rejections>().0,
+            vec![InventoryError::TooHeavy]
+        );
+    }
+
+    #[test]
+    fn transfer_fills_stacks_of_same_item() {
+        // given
+        let mut app = setup();
+        let e_player = setup_inventory(&mut app, 4, 25.0);
+        let e_stack = setup_item(&mut app, "energy_cell", 15);
+        transfer(&mut app, e_stack, e_player);
+        let e_item = setup_item(&mut app, "energy_cell", 10);
+
+        // when
+        transfer(&mut app, e_item, e_player);
+
+        // then
+        assert_eq!(app.get::<Quantity>(e_stack).0, 20);
+        assert_eq!(app.get::<Quantity>(e_item).0, 5);
+        assert_eq!(app.get::<Contains>(e_player).len(), 2);
+    }
+
+    #[test]
+    fn split_moves_amount_into_new_stack() {
+        // given
+        let mut app = setup();
+        let e_player = setup_inventory(&mut app, 2, 25.0);
+        let e_stack = setup_item(&mut app, "energy_cell", 10);
+        transfer(&mut app, e_stack, e_player);
+
+        // when
+        app.world_mut()
+            .trigger_targets(SplitStack { amount: 4 }, e_stack);
+        app.update();
+
+        // then
+        let stacks = app.get::<Contains>(e_player).to_vec();
+        assert_eq!(stacks.len(), 2);
+        assert_eq!(app.get::<Quantity>(e_stack).0, 6);
+        assert_eq!(app.get::<Quantity>(stacks[1]).0, 4);
+    }
+
+    #[test]
+    fn split_is_rejected_for_whole_stack() {
+        // given
+        let mut app = setup();
+        let e_player = setup_inventory(&mut app, 2, 25.0);
+        let e_stack = setup_item(&mut app, "energy_cell", 10);
+        transfer(&mut app, e_stack, e_player);
+
+        // when
+        app.world_mut()
+            .trigger_targets(SplitStack { amount: 10 }, e_stack);
+        app.update();
+
+        // then
+        assert_eq!(app.get::<Quantity>(e_stack).0, 10);
+        assert_eq!(
+            app.get_resource::<Rejections>().0,
+            vec![InventoryError::NotEnough]
+        );
+    }
+
+    #[test]
+    fn merge_empties_stack() {
+        // given
+        let mut app = setup();
+        let e_player = setup_inventory(&mut app, 2, 25.0);
+        let e_stack = setup_item(&mut app, "energy_cell", 10);
+        transfer(&mut app, e_stack, e_player);
+        app.world_mut()
+            .trigger_targets(SplitStack { amount: 4 }, e_stack);
+        app.update();
+        let e_split = app.get::<Contains>(e_player)[1];
+
+        // when
+        app.world_mut()
+            .trigger_targets(MergeStack { into: e_stack }, e_split);
+        app.update();
+
+        // then
+        assert_eq!(app.get::<Quantity>(e_stack).0, 10);
+        assert!(app.world().get_entity(e_split).is_err());
+    }
+
+    #[derive(Resource, Default)]
+    struct Rejections(Vec<InventoryError>);
+
+    fn setup() -> App {
+        let mut app = App::new();
+        app.insert_resource(ItemCatalog::from_ron(CATALOG).unwrap())
+            .init_resource::<Rejections>()
+            .add_plugins(InventoryPlugin)
+            .add_observer(
+                |trigger: Trigger<InventoryRejected>, mut rejections: ResMut<Rejections>| {
+                    rejections.0.push(trigger.event().error);
+                },
+            );
+        app
+    }
+
+    fn setup_inventory(app: &mut App, slots: usize, max_weight: f32) -> Entity {
+        app.world_mut()
+            .spawn((Player, Inventory { slots, max_weight }))
+            .id()
+    }
+
+    fn setup_item(app: &mut App, id: &str, quantity: u32) -> Entity {
+        app.world_mut()
+            .spawn((ItemId::new(id), Quantity(quantity)))
+            .id()
+    }
+
+    fn transfer(app: &mut App, item: Entity, container: Entity) {
+        app.world_mut()
+            .trigger_targets(TransferItem { container }, item);
+        app.update();
+    }
+}
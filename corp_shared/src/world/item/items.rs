@@ -2,11 +2,20 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Component, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[require(Quantity)]
 pub struct Item;
 
+/// How many of the item the stack holds.
+#[derive(Component, Serialize, Deserialize, Deref, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Quantity(pub u32);
+
+impl Default for Quantity {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Opens doors of other factions, given to items whose catalog entry has the behavior.
 #[derive(Component, Serialize, Deserialize, Clone, Debug, PartialEq)]
-#[require(Item, Name::new("Hacking Tool"))]
-#[cfg_attr(feature = "client", require(
-    StateScoped<crate::prelude::GameState> = StateScoped(crate::prelude::GameState::Playing))
-)]
+#[require(Item)]
 pub struct HackingTool;
@@ -1,7 +1,9 @@
+pub mod catalog;
 pub mod inventory;
 pub mod items;
 pub mod relation;
 
+pub use catalog::*;
 pub use inventory::*;
 pub use items::*;
 pub use relation::*;
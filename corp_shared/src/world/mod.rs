@@ -1,4 +1,5 @@
 pub mod colony;
+pub mod control;
 pub mod creature;
 pub mod faction;
 pub mod gameplay;
@@ -10,6 +11,7 @@ pub mod structure;
 pub mod r#use;
 
 pub use colony::*;
+pub use control::*;
 pub use creature::*;
 pub use faction::*;
 pub use gameplay::*;
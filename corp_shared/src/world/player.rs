@@ -4,9 +4,28 @@ use serde::{Deserialize, Serialize};
 #[derive(Component, Serialize, Deserialize, Clone, Debug)]
 pub struct Player;
 
+/// Fastest a player may move in meters per second, running speed with headroom for falling.
+pub const PLAYER_MAX_SPEED: f32 = 12.0;
+
 #[derive(Event, Serialize, Deserialize)]
 pub struct PlayerSpawnClientCommand;
 
+/// Position of the locally controlled player reported to the server.
+#[derive(Event, Serialize, Deserialize, Clone, Debug)]
+pub struct PlayerMoveClientCommand {
+    /// Increases with every command, the server drops commands that arrive late or reordered.
+    pub sequence: u32,
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
 /// A trigger from server that instructs the client to mark a specific entity as [`LocalPlayer`].
 #[derive(Event, Serialize, Deserialize)]
-pub struct SetupPlayerServerCommand;
+pub struct SetupPlayerServerCommand {
+    /// Spawn position picked by the server.
+    pub translation: Vec3,
+}
+
+/// Protects a freshly spawned player from damage until it runs out or the player acts.
+#[derive(Component, Debug)]
+pub struct SpawnProtection(pub Timer);
@@ -1,7 +1,8 @@
 use crate::prelude::Rank;
 use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Eq, PartialEq, Hash)]
+#[derive(Component, Serialize, Deserialize, Eq, PartialEq, Hash, Copy, Clone, Debug)]
 pub enum SecurityLevel {
     Low,
     Medium,
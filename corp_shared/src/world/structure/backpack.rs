@@ -1,9 +1,16 @@
 use crate::prelude::*;
 use avian3d::prelude::*;
-use bevy::prelude::*;
-use bevy_replicon::prelude::ClientTriggerExt;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
 use serde::{Deserialize, Serialize};
 
+/// How far from a backpack the server still lets a player loot it.
+pub const LOOT_RANGE: f32 = LOOKUP_RANGE + USE_RANGE_TOLERANCE;
+/// How long the player rummages in a backpack before taking items out of it.
+pub const LOOT_CHANNEL_SECS: f32 = 1.5;
+
 #[derive(Component, Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[require(
     Name::new("Backpack"),
@@ -41,8 +48,65 @@ pub struct LootCommand {
     pub action: LootAction,
 }
 
+impl MapEntities for LootCommand {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let LootAction::TakeItem(item) = &mut self.action {
+            *item = entity_mapper.get_mapped(*item);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum LootError {
+    OutOfRange,
+    NotInBackpack,
+}
+
+/// Item of a backpack as listed to the player looting it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LootEntry {
+    pub item: Entity,
+    pub id: Option<ItemId>,
+    pub quantity: u32,
+}
+
+/// Answer to [`LootAction::List`], triggered on the player and sent to its client.
+#[derive(Event, Serialize, Deserialize, Clone, Debug)]
+pub struct LootContents {
+    pub backpack: Entity,
+    pub items: Vec<LootEntry>,
+}
+
+impl MapEntities for LootContents {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.backpack = entity_mapper.get_mapped(self.backpack);
+        for entry in &mut self.items {
+            entry.item = entity_mapper.get_mapped(entry.item);
+        }
+    }
+}
+
+/// Outcome of taking items, triggered on the player and sent to its client.
+///
+/// Items the inventory of the player has no room for are reported with [`InventoryRejected`].
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct LootResult {
+    pub backpack: Entity,
+    pub action: LootAction,
+    pub error: Option<LootError>,
+}
+
+impl MapEntities for LootResult {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.backpack = entity_mapper.get_mapped(self.backpack);
+        if let LootAction::TakeItem(item) = &mut self.action {
+            *item = entity_mapper.get_mapped(*item);
+        }
+    }
+}
+
 pub fn on_use_backpack_event(trigger: Trigger<UseCommand>, mut commands: Commands) {
-    commands.client_trigger_targets(
+    commands.trigger_targets(
         LootCommand {
             user: trigger.event().user,
             action: LootAction::TakeAll,
@@ -51,6 +115,86 @@ pub fn on_use_backpack_event(trigger: Trigger<UseCommand>, mut commands: Command
     );
 }
 
+pub fn on_loot_command(
+    trigger: Trigger<LootCommand>,
+    q_backpack: Query<(&Transform, Option<&Contains>), With<Backpack>>,
+    q_user: Query<&Transform>,
+    q_item: Query<(Option<&ItemId>, &Quantity)>,
+    mut commands: Commands,
+) {
+    let &LootCommand { user, action } = trigger.event();
+    let backpack_e = trigger.target();
+    let Ok((backpack_tr, contains)) = q_backpack.get(backpack_e) else {
+        return;
+    };
+    let items = contains
+        .map(|contains| contains.to_vec())
+        .unwrap_or_default();
+    let mut result = LootResult {
+        backpack: backpack_e,
+        action,
+        error: None,
+    };
+    info!("Backpack {} action {:?} by {}", backpack_e, action, user);
+
+    let in_range = q_user
+        .get(user)
+        .is_ok_and(|user_tr| user_tr.translation.distance(backpack_tr.translation) <= LOOT_RANGE);
+    if !in_range {
+        warn!("Player {} is out of reach of backpack {}", user, backpack_e);
+        result.error = Some(LootError::OutOfRange);
+        commands.trigger_targets(result, user);
+        return;
+    }
+
+    match action {
+        LootAction::List => {
+            let items = items
+                .iter()
+                .filter_map(|item_e| {
+                    let (id, quantity) = q_item.get(*item_e).ok()?;
+                    Some(LootEntry {
+                        item: *item_e,
+                        id: id.cloned(),
+                        quantity: quantity.0,
+                    })
+                })
+                .collect();
+            commands.trigger_targets(
+                LootContents {
+                    backpack: backpack_e,
+                    items,
+                },
+                user,
+            );
+            return;
+        }
+        LootAction::TakeAll => {
+            for item_e in items {
+                commands.trigger_targets(TransferItem { container: user }, item_e);
+            }
+        }
+        LootAction::TakeItem(item_e) if items.contains(&item_e) => {
+            commands.trigger_targets(TransferItem { container: user }, item_e);
+        }
+        LootAction::TakeItem(item_e) => {
+            warn!("Item {} is not in backpack {}", item_e, backpack_e);
+            result.error = Some(LootError::NotInBackpack);
+        }
+    }
+    commands.trigger_targets(result, user);
+}
+
+pub fn despawn_empty_backpack(
+    mut commands: Commands,
+    q_entity_backpack: Query<Entity, (Without<Contains>, With<Backpack>)>,
+) {
+    for e_bag in &q_entity_backpack {
+        info!("Despawning empty backpack {:?}", e_bag);
+        commands.entity(e_bag).try_despawn();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,8 +233,9 @@ mod tests {
         app.update();
 
         // then
-        assert_eq!(app.get::<Contains>(e_player).into_iter().count(), 0);
+        assert!(!app.has_component::<Contains>(e_player));
         assert_eq!(app.get::<Contains>(e_backpack).into_iter().count(), 2);
+        assert_eq!(app.get_resource::<Listed>().0, vec![e_item_1, e_item_2]);
     }
 
     // This should now be an integration test
@@ -150,15 +295,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn player_cannot_take_item_from_other_backpack() {
+        // given
+        let (mut app, e_player) = setup();
+        let e_item_1 = app.world_mut().spawn(HackingTool).id();
+        let e_item_2 = app.world_mut().spawn(HackingTool).id();
+        let e_backpack = setup_backpack(&mut app, vec![e_item_1]);
+        setup_backpack(&mut app, vec![e_item_2]);
+
+        // when
+        app.world_mut().trigger_targets(
+            LootCommand {
+                user: e_player,
+                action: LootAction::TakeItem(e_item_2),
+            },
+            e_backpack,
+        );
+        app.update();
+
+        // then
+        assert!(!app.has_component::<Contains>(e_player));
+        assert_eq!(
+            app.get_resource::<Results>().0,
+            vec![Some(LootError::NotInBackpack)]
+        );
+    }
+
+    #[test]
+    fn player_out_of_range_cannot_loot() {
+        // given
+        let (mut app, e_player) = setup();
+        let e_item = app.world_mut().spawn(HackingTool).id();
+        let e_backpack = setup_backpack(&mut app, vec![e_item]);
+        app.get_mut::<Transform>(e_player).translation = Vec3::X * 10.0;
+
+        // when
+        app.world_mut().trigger_targets(
+            LootCommand {
+                user: e_player,
+                action: LootAction::TakeAll,
+            },
+            e_backpack,
+        );
+        app.update();
+
+        // then
+        assert!(!app.has_component::<Contains>(e_player));
+        assert_eq!(
+            app.get_resource::<Results>().0,
+            vec![Some(LootError::OutOfRange)]
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct Listed(Vec<Entity>);
+
+    #[derive(Resource, Default)]
+    struct Results(Vec<Option<LootError>>);
+
     fn setup_backpack(app: &mut App, items: Vec<Entity>) -> Entity {
         let backpack = app
             .world_mut()
             .spawn(Backpack)
             .observe(on_use_backpack_event)
+            .observe(on_loot_command)
             .id();
 
         for item in items {
-            app.world_mut().entity_mut(backpack).insert(StoredIn(item));
+            app.world_mut().entity_mut(item).insert(StoredIn(backpack));
         }
 
         backpack
@@ -166,8 +371,31 @@ mod tests {
 
     fn setup() -> (App, Entity) {
         let mut app = App::new();
-        app.init_time();
-        let player_entity = app.world_mut().spawn((Player, Inventory::default())).id();
+        app.init_time()
+            .init_resource::<ItemCatalog>()
+            .init_resource::<Listed>()
+            .init_resource::<Results>()
+            .add_plugins(InventoryPlugin)
+            .add_systems(Update, despawn_empty_backpack)
+            .add_observer(
+                |trigger: Trigger<LootContents>, mut listed: ResMut<Listed>| {
+                    listed.0 = trigger
+                        .event()
+                        .items
+                        .iter()
+                        .map(|entry| entry.item)
+                        .collect();
+                },
+            )
+            .add_observer(
+                |trigger: Trigger<LootResult>, mut results: ResMut<Results>| {
+                    results.0.push(trigger.event().error);
+                },
+            );
+        let player_entity = app
+            .world_mut()
+            .spawn((Player, Inventory::default(), Transform::default()))
+            .id();
         (app, player_entity)
     }
 }
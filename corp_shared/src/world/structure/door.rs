@@ -1,8 +1,10 @@
 use crate::prelude::*;
 use bevy::prelude::*;
-use bevy_replicon::prelude::ClientTriggerExt;
 use serde::{Deserialize, Serialize};
-use std::cmp::PartialEq;
+use std::{cmp::PartialEq, time::Duration};
+
+/// How long the player stands at a door terminal before the door reacts.
+pub const TERMINAL_USE_DURATION: Duration = Duration::from_secs(1);
 
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
@@ -11,27 +13,29 @@ use std::cmp::PartialEq;
     DynamicStructure,
     DoorState,
     SecurityLevel::Low,
-    OwnershipRegistry = lookup_door_ownership()
+    OwnershipRegistry
 )]
 pub struct Door;
 
-fn lookup_door_ownership() -> OwnershipRegistry {
-    OwnershipRegistry::new_permanent(Faction::EC)
-}
-
 #[derive(Component, Reflect, Debug, PartialEq, Eq)]
 #[reflect(Component)]
 pub struct DoorId(pub i32);
 
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
-#[require(Name::new("Door Terminal"), Structure, Use)]
+#[require(
+    Name::new("Door Terminal"),
+    Structure,
+    Use,
+    UseDuration(TERMINAL_USE_DURATION),
+    OwnershipRegistry
+)]
 #[cfg_attr(feature = "client", require(
     StateScoped<GameState> = StateScoped(GameState::Playing))
 )]
 pub struct DoorTerminal;
 
-#[derive(Component, Default, Eq, PartialEq, Debug, Clone)]
+#[derive(Component, Serialize, Deserialize, Default, Eq, PartialEq, Debug, Clone)]
 pub enum DoorState {
     Open {
         autoclose: Timer,
@@ -45,6 +49,8 @@ impl DoorState {
     const AUTOCLOSE_SECS: f32 = 10.0;
     const TOGGLE_BLOCK_SECS: f32 = 1.0;
     pub const HACK_DURATION_SECS: f32 = 60.0 * 5.0;
+    /// How long the hacker has to stand at the terminal.
+    pub const HACK_CHANNEL_SECS: f32 = 5.0;
     pub fn open() -> Self {
         DoorState::Open {
             autoclose: Timer::from_seconds(Self::AUTOCLOSE_SECS, TimerMode::Once),
@@ -74,8 +80,11 @@ impl DoorState {
 
 pub struct UseDoorEvent;
 
-#[derive(Deserialize, Event, Serialize, Clone, Debug)]
-pub struct DoorHackCommand;
+/// Triggered on a door when a user that doesn't own it tries to open it.
+#[derive(Event, Clone, Debug)]
+pub struct DoorHackCommand {
+    pub user: Entity,
+}
 
 #[derive(Deserialize, Event, Serialize, Clone, Debug)]
 pub enum DoorHackedEvent {
@@ -85,7 +94,8 @@ pub enum DoorHackedEvent {
 
 pub fn door_cooldown_system(mut q_door: Query<&mut DoorState, With<Door>>, r_time: Res<Time>) {
     for mut door_state in &mut q_door {
-        match *door_state {
+        // Open doors count down every tick, only closing is worth replicating
+        match *door_state.bypass_change_detection() {
             DoorState::Open {
                 ref mut autoclose,
                 ref mut toggle_block,
@@ -124,7 +134,12 @@ pub fn on_use_command(
                     }
                 }
             } else {
-                commands.client_trigger_targets(DoorHackCommand, trigger.target());
+                commands.trigger_targets(
+                    DoorHackCommand {
+                        user: trigger.event().user,
+                    },
+                    trigger.target(),
+                );
             }
         }
     }
@@ -184,6 +199,33 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn open_door_counting_down_is_not_a_change() {
+        // given
+        let mut app = setup();
+        let door_entity = setup_door(&mut app, Faction::EC, SecurityLevel::Low);
+        *app.get_mut::<DoorState>(door_entity) = DoorState::open();
+        app.update();
+        let opened_at = app
+            .world()
+            .entity(door_entity)
+            .get_ref::<DoorState>()
+            .unwrap()
+            .last_changed();
+
+        // when
+        app.update_after(Duration::from_secs_f32(1.0));
+
+        // then
+        let door_state = app
+            .world()
+            .entity(door_entity)
+            .get_ref::<DoorState>()
+            .unwrap();
+        assert!(door_state.is_open());
+        assert_eq!(door_state.last_changed(), opened_at);
+    }
+
     #[test]
     fn player_open_door() {
         // given
@@ -513,7 +555,11 @@ mod tests {
     fn setup_player(app: &mut App, items: Vec<Entity>, faction: Faction, rank: Rank) -> Entity {
         let player_entity = app
             .world_mut()
-            .spawn((Player, Inventory, PlayerFactionInfo { faction, rank }))
+            .spawn((
+                Player,
+                Inventory::default(),
+                PlayerFactionInfo { faction, rank },
+            ))
             .id();
         player_entity
     }
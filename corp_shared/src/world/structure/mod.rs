@@ -4,13 +4,25 @@ use bevy::prelude::*;
 pub use door::*;
 pub use plant::*;
 pub use prop::*;
+use serde::{Deserialize, Serialize};
+pub use spawn::*;
 pub use vortex::*;
+pub use zone::*;
 
 pub mod backpack;
 pub mod door;
 pub mod plant;
 pub mod prop;
+pub mod spawn;
 pub mod vortex;
+pub mod zone;
+
+/// Name of the colony scene node a replicated structure was spawned from.
+///
+/// Server and client load the same scene, the client pairs the replicated structure with its
+/// own copy by this name.
+#[derive(Component, Serialize, Deserialize, Eq, PartialEq, Clone, Debug)]
+pub struct SceneNodeName(pub String);
 
 pub struct StructurePlugin;
 
@@ -23,9 +35,11 @@ impl Plugin for StructurePlugin {
             .register_type::<Floor>()
             .register_type::<VortexGate>()
             .register_type::<VortexNode>()
+            .register_type::<SpawnPoint>()
             .register_type::<DoorId>()
             .register_type::<Door>()
             .register_type::<DoorTerminal>()
+            .register_type::<InterestZone>()
             .add_systems(
                 FixedUpdate,
                 (
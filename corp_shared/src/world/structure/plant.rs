@@ -1,8 +1,14 @@
 use crate::prelude::*;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::prelude::{Faction, OwnershipRegistry, SecurityLevel, UseCommand};
 
+/// Players closer than this to an energy node take part in its capture.
+pub const CAPTURE_RADIUS: f32 = 5.0;
+/// How long a faction has to hold an energy node alone to take it over.
+pub const CAPTURE_SECS: f32 = 30.0;
+
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 #[require(
@@ -10,7 +16,8 @@ use crate::prelude::{Faction, OwnershipRegistry, SecurityLevel, UseCommand};
     Structure,
     Use,
     SecurityLevel::Low,
-    OwnershipRegistry = lookup_ownership()
+    OwnershipRegistry = lookup_ownership(),
+    EnergyNodeState
 )]
 #[cfg_attr(feature = "client", require(
     StateScoped<GameState> = StateScoped(GameState::Playing))
@@ -21,8 +28,135 @@ fn lookup_ownership() -> OwnershipRegistry {
     OwnershipRegistry::new_permanent(Faction::EC)
 }
 
-pub fn on_use_territory_node_event(trigger: Trigger<UseCommand>) {
-    info!("Interaction with territory node: {:?}", trigger.target());
+/// Owner of the energy node and the capture under way, replicated to clients.
+#[derive(Component, Serialize, Deserialize, Default, PartialEq, Clone, Debug)]
+pub struct EnergyNodeState {
+    pub owner: Option<Faction>,
+    pub capture: Option<NodeCapture>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct NodeCapture {
+    pub faction: Faction,
+    /// Share of the capture done, from 0 to 1.
+    pub progress: f32,
+    /// Defenders or other factions are at the node, the capture doesn't progress.
+    pub contested: bool,
+}
+
+/// Triggered on an energy node when a faction starts taking it over.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct EnergyNodeCaptureStarted {
+    pub faction: Faction,
+}
+
+/// Triggered on an energy node when it changed hands.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct EnergyNodeCaptured {
+    pub previous: Option<Faction>,
+    pub faction: Faction,
+}
+
+pub fn on_use_territory_node_event(trigger: Trigger<UseCommand>, q_state: Query<&EnergyNodeState>) {
+    if let Ok(state) = q_state.get(trigger.target()) {
+        info!(
+            "Interaction with territory node {:?}: {:?}",
+            trigger.target(),
+            state
+        );
+    }
+}
+
+/// A faction alone at an energy node captures it over time, defenders or rivals contest it.
+pub fn capture_energy_nodes_system(
+    mut q_node: Query<
+        (
+            Entity,
+            &Transform,
+            &mut OwnershipRegistry,
+            &mut EnergyNodeState,
+        ),
+        With<EnergyNode>,
+    >,
+    q_player: Query<(&Transform, &PlayerFactionInfo), (With<Player>, Without<Dead>)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let step = time.delta_secs() / CAPTURE_SECS;
+    for (node_e, node_tr, mut registry, mut state) in &mut q_node {
+        let owner = registry.permanent_owner();
+        if state.owner != owner {
+            state.owner = owner;
+        }
+
+        let mut present = Vec::new();
+        for (player_tr, faction_info) in &q_player {
+            if player_tr.translation.distance(node_tr.translation) <= CAPTURE_RADIUS
+                && !present.contains(&faction_info.faction)
+            {
+                present.push(faction_info.faction);
+            }
+        }
+        let defended = owner.is_some_and(|owner| present.contains(&owner));
+        let attackers = present
+            .into_iter()
+            .filter(|faction| Some(*faction) != owner)
+            .collect::<Vec<_>>();
+
+        match attackers.as_slice() {
+            [] if state.capture.is_none() => {}
+            [] => {
+                if let Some(capture) = state.capture.as_mut() {
+                    capture.contested = false;
+                    capture.progress -= step;
+                    if capture.progress <= 0.0 {
+                        state.capture = None;
+                    }
+                }
+            }
+            [faction] if !defended => {
+                let faction = *faction;
+                let progress = match state.capture.as_mut() {
+                    Some(capture) if capture.faction == faction => {
+                        capture.contested = false;
+                        capture.progress += step;
+                        capture.progress
+                    }
+                    _ => {
+                        commands.trigger_targets(EnergyNodeCaptureStarted { faction }, node_e);
+                        state.capture = Some(NodeCapture {
+                            faction,
+                            progress: step,
+                            contested: false,
+                        });
+                        step
+                    }
+                };
+                if progress >= 1.0 {
+                    info!("Energy node {} captured by {}", node_e, faction);
+                    registry.set_permanent(faction);
+                    state.owner = Some(faction);
+                    state.capture = None;
+                    commands.trigger_targets(
+                        EnergyNodeCaptured {
+                            previous: owner,
+                            faction,
+                        },
+                        node_e,
+                    );
+                }
+            }
+            _ if state
+                .capture
+                .as_ref()
+                .is_none_or(|capture| capture.contested) => {}
+            _ => {
+                if let Some(capture) = state.capture.as_mut() {
+                    capture.contested = true;
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -35,6 +169,7 @@ mod tests {
     };
 
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn player_interacts_with_energy_node() {
@@ -52,6 +187,95 @@ mod tests {
         // It should show colony faction ownership, military and economy presence per faction
     }
 
+    #[test]
+    fn enemy_captures_undefended_node() {
+        // given
+        let mut app = setup();
+        app.add_systems(Update, capture_energy_nodes_system);
+        let e_energy_node = setup_territory_node(&mut app, Faction::EC, SecurityLevel::Low);
+        let e_player = setup_player(&mut app, Vec::new(), Faction::CMG, Rank::R1);
+        app.world_mut()
+            .entity_mut(e_player)
+            .insert(Transform::default());
+
+        // when
+        app.update_after(Duration::from_secs_f32(CAPTURE_SECS / 2.0));
+        let progress = app.get::<EnergyNodeState>(e_energy_node).capture.clone();
+        app.update_after(Duration::from_secs_f32(CAPTURE_SECS / 2.0));
+
+        // then
+        assert_eq!(
+            progress,
+            Some(NodeCapture {
+                faction: Faction::CMG,
+                progress: 0.5,
+                contested: false,
+            })
+        );
+        let state = app.get::<EnergyNodeState>(e_energy_node);
+        assert_eq!(state.owner, Some(Faction::CMG));
+        assert_eq!(state.capture, None);
+        assert_eq!(
+            app.get::<OwnershipRegistry>(e_energy_node)
+                .permanent_owner(),
+            Some(Faction::CMG)
+        );
+    }
+
+    #[test]
+    fn defenders_contest_capture() {
+        // given
+        let mut app = setup();
+        app.add_systems(Update, capture_energy_nodes_system);
+        let e_energy_node = setup_territory_node(&mut app, Faction::EC, SecurityLevel::Low);
+        let e_attacker = setup_player(&mut app, Vec::new(), Faction::CMG, Rank::R1);
+        app.world_mut()
+            .entity_mut(e_attacker)
+            .insert(Transform::default());
+        app.update_after(Duration::from_secs_f32(CAPTURE_SECS / 2.0));
+
+        // when
+        let e_defender = setup_player(&mut app, Vec::new(), Faction::EC, Rank::R1);
+        app.world_mut()
+            .entity_mut(e_defender)
+            .insert(Transform::default());
+        app.update_after(Duration::from_secs_f32(CAPTURE_SECS));
+
+        // then
+        let state = app.get::<EnergyNodeState>(e_energy_node);
+        assert_eq!(state.owner, Some(Faction::EC));
+        assert_eq!(
+            state.capture,
+            Some(NodeCapture {
+                faction: Faction::CMG,
+                progress: 0.5,
+                contested: true,
+            })
+        );
+    }
+
+    #[test]
+    fn capture_fades_when_attackers_leave() {
+        // given
+        let mut app = setup();
+        app.add_systems(Update, capture_energy_nodes_system);
+        let e_energy_node = setup_territory_node(&mut app, Faction::EC, SecurityLevel::Low);
+        let e_player = setup_player(&mut app, Vec::new(), Faction::CMG, Rank::R1);
+        app.world_mut()
+            .entity_mut(e_player)
+            .insert(Transform::default());
+        app.update_after(Duration::from_secs_f32(CAPTURE_SECS / 2.0));
+
+        // when
+        app.get_mut::<Transform>(e_player).translation = Vec3::X * CAPTURE_RADIUS * 2.0;
+        app.update_after(Duration::from_secs_f32(CAPTURE_SECS));
+
+        // then
+        let state = app.get::<EnergyNodeState>(e_energy_node);
+        assert_eq!(state.owner, Some(Faction::EC));
+        assert_eq!(state.capture, None);
+    }
+
     fn setup() -> App {
         let mut app = App::new();
         app.init_time();
@@ -60,14 +284,21 @@ mod tests {
 
     fn setup_territory_node(app: &mut App, faction: Faction, level: SecurityLevel) -> Entity {
         let ownership = OwnershipRegistry::new_permanent(faction);
-        let door_entity = app.world_mut().spawn((EnergyNode, ownership, level)).id();
+        let door_entity = app
+            .world_mut()
+            .spawn((EnergyNode, ownership, level, Transform::default()))
+            .id();
         door_entity
     }
 
     fn setup_player(app: &mut App, items: Vec<Entity>, faction: Faction, rank: Rank) -> Entity {
         let player_entity = app
             .world_mut()
-            .spawn((Player, Inventory, PlayerFactionInfo { faction, rank }))
+            .spawn((
+                Player,
+                Inventory::default(),
+                PlayerFactionInfo { faction, rank },
+            ))
             .id();
         player_entity
     }
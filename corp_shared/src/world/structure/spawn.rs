@@ -0,0 +1,17 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Place in a colony where players enter the world.
+///
+/// Every [`VortexNode`] is a spawn point for all factions, points reserved for a faction are
+/// placed in the scene with this component.
+#[derive(Component, Default, Reflect, Debug)]
+#[reflect(Component)]
+#[cfg_attr(feature = "client", require(
+    StateScoped<GameState> = StateScoped(GameState::Playing))
+)]
+pub struct SpawnPoint {
+    /// Only players of the faction spawn here, any faction when not set.
+    #[reflect(default)]
+    pub faction: Option<Faction>,
+}
@@ -4,11 +4,15 @@ use bevy::prelude::*;
 
 #[derive(Component, Default, Reflect, Debug)]
 #[reflect(Component)]
-#[require(Name::new("Vortex Node"))]
+#[require(Name::new("Vortex Node"), SpawnPoint)]
 #[cfg_attr(feature = "client", require(
     StateScoped<GameState> = StateScoped(GameState::Playing))
 )]
-pub struct VortexNode;
+pub struct VortexNode {
+    /// Identifies the node within its colony, gates of other colonies point at it.
+    #[reflect(default)]
+    pub id: u32,
+}
 
 #[derive(Component, Default, Reflect, Debug)]
 #[reflect(Component)]
@@ -21,7 +25,31 @@ pub struct VortexNode;
 #[cfg_attr(feature = "client", require(
     StateScoped<GameState> = StateScoped(GameState::Playing))
 )]
-pub struct VortexGate;
+pub struct VortexGate {
+    /// Colony the gate leads to.
+    #[reflect(default)]
+    pub colony: Colony,
+    /// [`VortexNode`] the player arrives at, any node of the colony when not set.
+    #[reflect(default)]
+    pub node: Option<u32>,
+    /// Only players of the faction may pass, anyone when not set.
+    #[reflect(default)]
+    pub faction: Option<Faction>,
+    /// Lowest rank that may pass.
+    #[reflect(default)]
+    pub rank: Rank,
+}
+
+impl VortexGate {
+    pub fn allows(&self, faction_info: Option<&PlayerFactionInfo>) -> bool {
+        match (self.faction, faction_info) {
+            (None, None) => self.rank == Rank::R0,
+            (None, Some(info)) => info.rank >= self.rank,
+            (Some(faction), Some(info)) => info.faction == faction && info.rank >= self.rank,
+            (Some(_), None) => false,
+        }
+    }
+}
 
 fn vortex_gate_collider() -> Collider {
     Collider::cuboid(2.0, 2.0, 2.0)
@@ -30,3 +58,39 @@ fn vortex_gate_collider() -> Collider {
 fn vortex_gate_collision_layers() -> CollisionLayers {
     CollisionLayers::new([GameLayer::Sensor], [GameLayer::Player])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gate_allows_player_of_faction_and_rank() {
+        // given
+        let gate = VortexGate {
+            faction: Some(Faction::EC),
+            rank: Rank::R2,
+            ..default()
+        };
+        let info = |faction, rank| PlayerFactionInfo { faction, rank };
+
+        // when / then
+        assert!(gate.allows(Some(&info(Faction::EC, Rank::R2))));
+        assert!(gate.allows(Some(&info(Faction::EC, Rank::R5))));
+        assert!(!gate.allows(Some(&info(Faction::EC, Rank::R1))));
+        assert!(!gate.allows(Some(&info(Faction::VI, Rank::R5))));
+        assert!(!gate.allows(None));
+    }
+
+    #[test]
+    fn open_gate_allows_anyone() {
+        // given
+        let gate = VortexGate::default();
+
+        // when / then
+        assert!(gate.allows(None));
+        assert!(gate.allows(Some(&PlayerFactionInfo {
+            faction: Faction::CMG,
+            rank: Rank::R0,
+        })));
+    }
+}
@@ -0,0 +1,24 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Part of a colony that can't be seen into from the outside, like the inside of a building.
+///
+/// Placed in the scene as a box around the node. Players and backpacks inside a zone are only
+/// replicated to clients in the same zone.
+#[derive(Component, Default, Reflect, Debug)]
+#[reflect(Component)]
+#[cfg_attr(feature = "client", require(
+    StateScoped<GameState> = StateScoped(GameState::Playing))
+)]
+pub struct InterestZone {
+    /// Half the size of the box in the space of the node.
+    pub half_extents: Vec3,
+}
+
+impl InterestZone {
+    pub fn contains(&self, transform: &Transform, position: Vec3) -> bool {
+        let local =
+            transform.rotation.inverse() * (position - transform.translation) / transform.scale;
+        local.abs().cmple(self.half_extents).all()
+    }
+}
@@ -1,7 +1,30 @@
-use bevy::prelude::*;
+use crate::prelude::GameLayer;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How far a player reaches to use something.
+pub const LOOKUP_RANGE: f32 = 2.0;
+/// Positions of client and server drift apart by the time a request arrives.
+pub const USE_RANGE_TOLERANCE: f32 = 0.5;
+/// Layers of the entities a player can use, and of the ones blocking the view to them.
+pub const USABLE_LAYERS: [GameLayer; 2] = [GameLayer::Structure, GameLayer::Sensor];
+
+/// Rays looking for usable entities start at the belt of the player.
+pub fn use_origin(player_translation: Vec3) -> Vec3 {
+    player_translation - Vec3::Y / 2.0
+}
+
 #[derive(Component, Debug, Default)]
 pub struct Use;
 
+/// Using the entity takes time, the player channels it before the [`UseCommand`] is triggered.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct UseDuration(pub Duration);
+
 #[derive(Debug, Event)]
 pub struct UseCommand {
     pub user: Entity,
@@ -12,3 +35,15 @@ impl UseCommand {
         Self { user }
     }
 }
+
+/// Asks the server to use the target, it triggers a [`UseCommand`] on it if the player can reach it.
+#[derive(Event, Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct UseRequest {
+    pub target: Entity,
+}
+
+impl MapEntities for UseRequest {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target = entity_mapper.get_mapped(self.target);
+    }
+}
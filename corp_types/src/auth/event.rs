@@ -27,6 +27,15 @@ impl AuthenticationEvent {
             timestamp: Utc::now(),
         }
     }
+
+    pub fn token_expired_event(token: impl Into<String>) -> Self {
+        Self {
+            event_type: AuthEventType::TokenExpired(TokenData {
+                token: token.into(),
+            }),
+            timestamp: Utc::now(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]